@@ -8,12 +8,14 @@ pub enum BufferMessage {
     Modification(usize, TextModification),
     MoveCursor(usize, CursorDirection),
     MoveViewPort(ViewPortDirection),
+    Redo,
     RemoveLine(usize),
     ResetCursor,
     SaveBuffer,
     SetContent(Vec<BufferLine>),
     SetCursorToLineContent(String),
     SortContent(fn(&BufferLine, &BufferLine) -> Ordering),
+    Undo,
     UpdateViewPortByCursor,
 }
 
@@ -36,6 +38,7 @@ impl std::fmt::Debug for BufferMessage {
             BufferMessage::MoveViewPort(direction) => {
                 f.debug_tuple("MoveViewPort").field(direction).finish()
             }
+            BufferMessage::Redo => f.debug_tuple("Redo").finish(),
             BufferMessage::RemoveLine(index) => f.debug_tuple("RemoveLine").field(index).finish(),
             BufferMessage::ResetCursor => f.debug_tuple("ResetCursor").finish(),
             BufferMessage::SaveBuffer => f.debug_tuple("SaveBuffer").finish(),
@@ -48,6 +51,7 @@ impl std::fmt::Debug for BufferMessage {
                 .debug_tuple("SortContent")
                 .field(&"fn(&BufferLine, &BufferLine) -> Ordering")
                 .finish(),
+            BufferMessage::Undo => f.debug_tuple("Undo").finish(),
             BufferMessage::UpdateViewPortByCursor => {
                 f.debug_tuple("UpdateViewPortByCursor").finish()
             }
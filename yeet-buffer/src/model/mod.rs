@@ -16,6 +16,7 @@ pub enum Mode {
     #[default]
     Navigation,
     Normal,
+    VisualLine,
 }
 
 impl Mode {
@@ -31,6 +32,7 @@ impl Display for Mode {
             Mode::Insert => "insert".to_string(),
             Mode::Navigation => "navigation".to_string(),
             Mode::Normal => "normal".to_string(),
+            Mode::VisualLine => "visual line".to_string(),
         };
 
         write!(f, "{}", content)
@@ -69,6 +71,7 @@ impl std::fmt::Debug for Buffer {
 
 #[derive(Clone, Debug, Default)]
 pub struct Cursor {
+    pub anchor: Option<usize>,
     pub hide_cursor: bool,
     pub hide_cursor_line: bool,
     pub horizontal_index: CursorPosition,
@@ -97,6 +100,7 @@ pub struct BufferLine {
     pub content: Ansi,
     pub search_char_position: Option<Vec<(usize, usize)>>,
     pub signs: Vec<Sign>,
+    pub suffix: Option<String>,
 }
 
 impl BufferLine {
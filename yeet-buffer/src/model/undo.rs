@@ -65,13 +65,16 @@ impl Undo {
             0
         };
 
-        let end = if let Some(index) = self.current_transaction_index {
-            index
-        } else {
-            self.transactions.len() - 1
+        let end = match self.current_transaction_index {
+            Some(index) => index + 1,
+            None => 0,
         };
 
-        self.transactions[start..end + 1]
+        if start >= end {
+            return Vec::new();
+        }
+
+        self.transactions[start..end]
             .iter()
             .fold(Vec::new(), |mut acc, t| {
                 acc.extend(t.changes.clone());
@@ -88,15 +91,56 @@ impl Undo {
 
         let changes = self.get_uncommited_changes();
 
-        self.current_save_index = if let Some(index) = self.current_transaction_index {
-            Some(index)
-        } else {
-            Some(self.transactions.len() - 1)
-        };
+        self.current_save_index = self.current_transaction_index;
 
         changes
     }
 
+    pub fn undo(&mut self) -> Option<Vec<BufferChanged>> {
+        self.close_transaction();
+
+        let index = self.current_transaction_index?;
+        // NOTE: saved transactions are persisted to disk already
+        if self.current_save_index == Some(index) {
+            return None;
+        }
+
+        self.current_transaction_index = index.checked_sub(1);
+
+        let reverted = self.transactions[index]
+            .changes
+            .iter()
+            .rev()
+            .map(|change| match change {
+                BufferChanged::Content(ln, old, new) => {
+                    BufferChanged::Content(*ln, new.clone(), old.clone())
+                }
+                BufferChanged::LineAdded(ln, cntnt) => {
+                    BufferChanged::LineRemoved(*ln, cntnt.clone())
+                }
+                BufferChanged::LineRemoved(ln, cntnt) => {
+                    BufferChanged::LineAdded(*ln, cntnt.clone())
+                }
+            })
+            .collect();
+
+        Some(reverted)
+    }
+
+    pub fn redo(&mut self) -> Option<Vec<BufferChanged>> {
+        self.close_transaction();
+
+        let index = match self.current_transaction_index {
+            Some(index) => index + 1,
+            None => 0,
+        };
+
+        let transaction = self.transactions.get(index)?;
+        self.current_transaction_index = Some(index);
+
+        Some(transaction.changes.clone())
+    }
+
     fn add_transaction(&mut self, changes: Vec<BufferChanged>) {
         if changes.is_empty() {
             return;
@@ -107,6 +151,12 @@ impl Undo {
             Err(_) => 0,
         };
 
+        let applied = match self.current_transaction_index {
+            Some(index) => index + 1,
+            None => 0,
+        };
+        self.transactions.truncate(applied);
+
         self.transactions.push(Transaction {
             changes,
             _timestamp: timestamp,
@@ -329,6 +379,62 @@ mod test {
         assert_eq!(changes, vec![]);
     }
 
+    #[test]
+    fn undo_and_redo() {
+        use crate::model::ansi::Ansi;
+        use crate::model::undo::BufferChanged;
+
+        let mut undo = super::Undo::default();
+        assert_eq!(undo.undo(), None);
+        assert_eq!(undo.redo(), None);
+
+        undo.add(
+            &crate::model::Mode::Normal,
+            vec![BufferChanged::LineRemoved(1, Ansi::new("a"))],
+        );
+        undo.save();
+
+        undo.add(
+            &crate::model::Mode::Normal,
+            vec![
+                BufferChanged::Content(0, Ansi::new("b"), Ansi::new("c")),
+                BufferChanged::LineAdded(1, Ansi::new("d")),
+            ],
+        );
+
+        let changes = undo.undo();
+        assert_eq!(
+            changes,
+            Some(vec![
+                BufferChanged::LineRemoved(1, Ansi::new("d")),
+                BufferChanged::Content(0, Ansi::new("c"), Ansi::new("b")),
+            ])
+        );
+        assert_eq!(undo.get_uncommited_changes(), vec![]);
+        assert_eq!(undo.undo(), None);
+
+        let changes = undo.redo();
+        assert_eq!(
+            changes,
+            Some(vec![
+                BufferChanged::Content(0, Ansi::new("b"), Ansi::new("c")),
+                BufferChanged::LineAdded(1, Ansi::new("d")),
+            ])
+        );
+        assert_eq!(undo.redo(), None);
+
+        undo.undo();
+        undo.add(
+            &crate::model::Mode::Normal,
+            vec![BufferChanged::LineAdded(0, Ansi::new("e"))],
+        );
+        assert_eq!(undo.redo(), None);
+        assert_eq!(
+            undo.save(),
+            vec![BufferChanged::LineAdded(0, Ansi::new("e"))]
+        );
+    }
+
     #[test]
     fn consolidate() {
         use crate::model::ansi::Ansi;
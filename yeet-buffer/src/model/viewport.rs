@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use super::{BufferLine, SignIdentifier};
+use super::{ansi::Ansi, BufferLine, SignIdentifier};
 
 #[derive(Debug, Default)]
 pub struct WindowSettings {
@@ -14,6 +14,7 @@ pub struct ViewPort {
     pub horizontal_index: usize,
     pub line_number: LineNumber,
     pub line_number_width: usize,
+    pub show_suffix: bool,
     pub sign_column_width: usize,
     pub vertical_index: usize,
     pub width: usize,
@@ -54,13 +55,23 @@ impl ViewPort {
             0
         };
 
-        self.get_prefix_width() + self.get_border_width() + custom_prefix_width
+        self.get_prefix_width()
+            + self.get_border_width()
+            + custom_prefix_width
+            + self.get_suffix_width(line)
     }
 
     fn get_prefix_width(&self) -> usize {
         self.sign_column_width + self.get_line_number_width()
     }
 
+    pub fn get_suffix_width(&self, line: &BufferLine) -> usize {
+        match &line.suffix {
+            Some(suffix) if self.show_suffix => Ansi::new(suffix).count_chars(),
+            _ => 0,
+        }
+    }
+
     pub fn set(&mut self, settings: &WindowSettings) {
         self.sign_column_width = settings.sign_column_width;
     }
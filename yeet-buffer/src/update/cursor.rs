@@ -231,6 +231,12 @@ pub fn set_outbound_cursor_to_inbound_position(cursor: &mut Cursor, mode: &Mode,
             cursor.vertical_index = max_index;
         }
 
+        if let Some(anchor) = cursor.anchor.as_mut() {
+            if *anchor > max_index {
+                *anchor = max_index;
+            }
+        }
+
         let line = match model.lines.get(cursor.vertical_index) {
             Some(line) => line,
             None => return,
@@ -279,6 +285,7 @@ fn get_index_correction(mode: &Mode) -> usize {
         Mode::Insert => 0,
         Mode::Navigation => 1,
         Mode::Normal => 1,
+        Mode::VisualLine => 1,
     }
 }
 
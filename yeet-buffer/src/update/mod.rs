@@ -30,6 +30,18 @@ pub fn update_buffer(
                     update_cursor_by_direction(cursor, mode, buffer, &1, &CursorDirection::Left);
                 }
             }
+
+            if let Some(cursor) = cursor {
+                match to {
+                    Mode::VisualLine => {
+                        if from != &Mode::VisualLine && !from.is_command() {
+                            cursor.anchor = Some(cursor.vertical_index);
+                        }
+                    }
+                    Mode::Command(_) => {}
+                    Mode::Insert | Mode::Navigation | Mode::Normal => cursor.anchor = None,
+                }
+            }
             Vec::new()
         }
         BufferMessage::Modification(count, modification) => {
@@ -53,6 +65,12 @@ pub fn update_buffer(
             viewport::update_by_direction(viewport, cursor, buffer, direction);
             Vec::new()
         }
+        BufferMessage::Redo => {
+            if let Some(changes) = buffer.undo.redo() {
+                modification::apply(cursor, mode, buffer, &changes);
+            }
+            Vec::new()
+        }
         BufferMessage::RemoveLine(index) => {
             buffer.lines.remove(*index);
 
@@ -130,6 +148,12 @@ pub fn update_buffer(
             }
             Vec::new()
         }
+        BufferMessage::Undo => {
+            if let Some(changes) = buffer.undo.undo() {
+                modification::apply(cursor, mode, buffer, &changes);
+            }
+            Vec::new()
+        }
         BufferMessage::UpdateViewPortByCursor => Vec::new(),
     };
 
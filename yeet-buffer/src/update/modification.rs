@@ -211,6 +211,49 @@ pub fn update(
     }
 }
 
+pub fn apply(
+    cursor: &mut Option<Cursor>,
+    mode: &Mode,
+    buffer: &mut Buffer,
+    changes: &Vec<BufferChanged>,
+) {
+    let mut changed_index = None;
+    for change in changes {
+        match change {
+            BufferChanged::Content(index, _, new) => {
+                if let Some(line) = buffer.lines.get_mut(*index) {
+                    line.content = new.clone();
+                    changed_index = Some(*index);
+                }
+            }
+            BufferChanged::LineAdded(index, content) => {
+                let index = (*index).min(buffer.lines.len());
+                buffer.lines.insert(
+                    index,
+                    BufferLine {
+                        content: content.clone(),
+                        ..Default::default()
+                    },
+                );
+                changed_index = Some(index);
+            }
+            BufferChanged::LineRemoved(index, _) => {
+                if *index < buffer.lines.len() {
+                    buffer.lines.remove(*index);
+                    changed_index = Some(*index);
+                }
+            }
+        }
+    }
+
+    if let Some(cursor) = cursor {
+        if let Some(index) = changed_index {
+            cursor.vertical_index = index;
+        }
+        cursor::set_outbound_cursor_to_inbound_position(cursor, mode, buffer);
+    }
+}
+
 fn is_inclusive(motion: &CursorDirection) -> bool {
     match motion {
         CursorDirection::Left
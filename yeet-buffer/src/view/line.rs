@@ -9,7 +9,13 @@ pub fn add_line_styles(
 ) -> Ansi {
     let content_width = vp.get_content_width(line);
     let ansi = line.content.skip_chars(vp.horizontal_index);
+    let ansi = if vp.get_suffix_width(line) > 0 {
+        ansi.take_chars(content_width)
+    } else {
+        ansi
+    };
     let ansi = add_search_styles(line, &ansi);
+    let ansi = add_selection_styles(vp, cursor, index, content_width, &ansi);
 
     add_cursor_styles(vp, mode, cursor, index, content_width, &ansi)
 }
@@ -32,6 +38,44 @@ fn add_search_styles(line: &BufferLine, ansi: &Ansi) -> Ansi {
     }
 }
 
+fn add_selection_styles(
+    vp: &ViewPort,
+    cursor: &Option<Cursor>,
+    index: &usize,
+    content_width: usize,
+    ansi: &Ansi,
+) -> Ansi {
+    let (anchor, vertical_index) = match cursor {
+        Some(Cursor {
+            anchor: Some(anchor),
+            vertical_index,
+            ..
+        }) => (*anchor, *vertical_index),
+        _ => return ansi.clone(),
+    };
+
+    let corrected_index = index + vp.vertical_index;
+    let (start, end) = if anchor < vertical_index {
+        (anchor, vertical_index)
+    } else {
+        (vertical_index, anchor)
+    };
+
+    if corrected_index < start || corrected_index > end || corrected_index == vertical_index {
+        return ansi.clone();
+    }
+
+    let mut content = ansi.clone();
+    let char_count = content.count_chars();
+    let repeat_count = content_width.saturating_sub(char_count);
+
+    content.prepend("\x1b[44m");
+    content.append(" ".repeat(repeat_count).as_str());
+    content.append("\x1b[0m");
+
+    content
+}
+
 fn add_cursor_styles(
     vp: &ViewPort,
     mode: &Mode,
@@ -91,7 +135,7 @@ fn add_cursor_styles(
         let (code, reset) = match mode {
             Mode::Command(_) | Mode::Normal => ("\x1b[7m", reset.as_str()),
             Mode::Insert => ("\x1b[4m", reset.as_str()),
-            Mode::Navigation => ("", ""),
+            Mode::Navigation | Mode::VisualLine => ("", ""),
         };
 
         content.insert(cursor_index, code);
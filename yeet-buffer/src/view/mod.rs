@@ -12,6 +12,7 @@ use crate::model::{ansi::Ansi, viewport::ViewPort, Buffer, BufferLine, Cursor, M
 mod line;
 mod prefix;
 mod style;
+mod suffix;
 
 // FIX: long lines break viewport content
 pub fn view(
@@ -69,12 +70,14 @@ fn get_styled_lines<'a>(
     for (i, mut bl) in lines.into_iter().enumerate() {
         let corrected_index = i + vp.vertical_index;
 
+        let line = line::add_line_styles(vp, mode, cursor, &i, &mut bl);
         let content = Ansi::new("")
             .join(&prefix::get_signs(vp, &bl))
             .join(&prefix::get_line_number(vp, corrected_index, cursor))
             .join(&prefix::get_custom_prefix(&bl))
             .join(&prefix::get_border(vp))
-            .join(&line::add_line_styles(vp, mode, cursor, &i, &mut bl));
+            .join(&line)
+            .join(&suffix::get_custom_suffix(vp, &bl, &line));
 
         if let Ok(text) = content.to_string().into_text() {
             result.push(text.lines);
@@ -0,0 +1,15 @@
+use crate::model::{ansi::Ansi, viewport::ViewPort, BufferLine};
+
+pub fn get_custom_suffix(vp: &ViewPort, line: &BufferLine, content: &Ansi) -> Ansi {
+    let suffix = match &line.suffix {
+        Some(suffix) if vp.show_suffix => suffix,
+        _ => return Ansi::new(""),
+    };
+
+    // NOTE: content is only padded to full width on cursor and selected lines
+    let padding = vp
+        .get_content_width(line)
+        .saturating_sub(content.count_chars());
+
+    Ansi::new(&format!("\x1b[0m{}{}", " ".repeat(padding), suffix))
+}
@@ -1,31 +1,39 @@
 use std::{
-    io::{stdout, Write},
-    path::PathBuf,
+    io::{stderr, stdin, stdout, Write},
+    path::{Path, PathBuf},
+    process::ExitStatus,
 };
 
 use tokio::fs;
 use yeet_buffer::message::BufferMessage;
-use yeet_keymap::message::{KeymapMessage, QuitMode};
+use yeet_keymap::message::{KeymapMessage, PrintContent, QuitMode};
 
 use crate::{
+    editor,
     error::AppError,
     event::{Emitter, Message},
     init::{history, mark, qfix},
     model::{DirectoryBufferState, Model, WindowType},
     open,
+    settings::OpenerRule,
+    shell,
     task::Task,
     terminal::TerminalWrapper,
-    update::{self, viewport},
+    update::{self, bulkrename, viewport},
 };
 
 #[derive(Debug)]
 pub enum Action {
+    BulkRename(PathBuf, Vec<PathBuf>),
     EmitMessages(Vec<Message>),
     Load(WindowType, PathBuf, Option<String>),
     ModeChanged,
     Open(PathBuf),
+    OpenInForeground(PathBuf),
+    OpenWith(PathBuf, OpenerRule),
     Quit(QuitMode, Option<String>),
     Resize(u16, u16),
+    Shell(PathBuf, String),
     Task(Task),
     UnwatchPath(PathBuf),
     WatchPath(PathBuf),
@@ -69,7 +77,14 @@ pub async fn postview(
 
 fn is_preview_action(action: &Action) -> bool {
     match action {
-        Action::Load(_, _, _) | Action::Open(_) | Action::Resize(_, _) | Action::Task(_) => true,
+        Action::BulkRename(_, _)
+        | Action::Load(_, _, _)
+        | Action::Open(_)
+        | Action::OpenInForeground(_)
+        | Action::OpenWith(_, _)
+        | Action::Resize(_, _)
+        | Action::Shell(_, _)
+        | Action::Task(_) => true,
 
         Action::EmitMessages(_)
         | Action::ModeChanged
@@ -110,6 +125,21 @@ async fn execute(
         tracing::debug!("handling action: {:?}", action);
 
         match action {
+            Action::BulkRename(base, paths) => {
+                emitter.suspend();
+                terminal.suspend();
+
+                let result = edit_paths(&base, &paths, emitter.remote.as_deref()).await;
+
+                emitter.resume();
+                terminal.resume()?;
+
+                let message = match result {
+                    Ok(lines) => Message::BulkRenameEdited(base, paths, lines),
+                    Err(err) => Message::Error(err.to_string()),
+                };
+                emitter.run(Task::EmitMessages(vec![message]));
+            }
             Action::EmitMessages(messages) => {
                 emitter.run(Task::EmitMessages(messages));
             }
@@ -162,10 +192,74 @@ async fn execute(
                 terminal.suspend();
 
                 // TODO: remove flickering (alternate screen leave and cli started)
-                open::path(&path).await?;
+                open::path(&path, emitter.remote.as_deref()).await?;
+
+                emitter.resume();
+                terminal.resume()?;
+            }
+            Action::OpenInForeground(path) => {
+                emitter.suspend();
+                terminal.suspend();
+
+                let result = editor::path(&path, emitter.remote.as_deref()).await;
 
                 emitter.resume();
                 terminal.resume()?;
+
+                let mut messages = vec![Message::Rerender];
+                match result {
+                    Ok(status) if !status.success() => {
+                        messages.push(Message::Error(format!("editor exited with {}", status)));
+                    }
+                    Ok(_) => {}
+                    Err(err) => messages.push(Message::Error(err.to_string())),
+                }
+                emitter.run(Task::EmitMessages(messages));
+
+                // NOTE: the file was probably changed in the editor
+                emitter.run(Task::LoadPreview(path, model.layout.preview));
+            }
+            Action::OpenWith(path, rule) => {
+                let base = match path.parent() {
+                    Some(it) => it.to_path_buf(),
+                    None => path.clone(),
+                };
+                let command = shell::with_path(&rule.command, &path);
+
+                if rule.foreground {
+                    emitter.suspend();
+                    terminal.suspend();
+
+                    let result =
+                        shell::foreground(&base, &command, emitter.remote.as_deref()).await;
+
+                    emitter.resume();
+                    terminal.resume()?;
+
+                    let mut messages = vec![Message::Rerender];
+                    match result {
+                        Ok(status) if !status.success() => messages.push(Message::Error(format!(
+                            "{} exited with {}",
+                            rule.command, status
+                        ))),
+                        Ok(_) => {}
+                        Err(err) => messages.push(Message::Error(err.to_string())),
+                    }
+                    emitter.run(Task::EmitMessages(messages));
+                } else {
+                    let result = shell::background(
+                        &base,
+                        &command,
+                        rule.detach,
+                        rule.block,
+                        emitter.remote.as_deref(),
+                    )
+                    .await;
+                    if let Err(err) = result {
+                        let message = format!("opening {:?} failed: {}", path, err);
+                        emitter.run(Task::EmitMessages(vec![Message::Error(message)]));
+                    }
+                }
             }
             Action::Quit(mode, stdout_result) => {
                 if let Some(stdout_result) = stdout_result {
@@ -203,6 +297,25 @@ async fn execute(
                     emitter.run(Task::LoadPreview(path.to_path_buf(), model.layout.preview));
                 }
             }
+            Action::Shell(base, command) => {
+                emitter.suspend();
+                terminal.suspend();
+
+                let result = run_in_foreground(&base, &command, emitter.remote.as_deref()).await;
+
+                emitter.resume();
+                terminal.resume()?;
+
+                let content = match result {
+                    Ok(status) if status.success() => Vec::new(),
+                    Ok(status) => vec![
+                        PrintContent::Default(format!(":!!{}", command)),
+                        PrintContent::Error(format!("shell returned {}", status)),
+                    ],
+                    Err(err) => vec![PrintContent::Error(err.to_string())],
+                };
+                emitter.run(Task::EmitMessages(vec![Message::ShellFinished(content)]));
+            }
             Action::Task(task) => emitter.run(task),
             Action::UnwatchPath(path) => {
                 if path == PathBuf::default() {
@@ -251,3 +364,54 @@ fn contains_quit(actions: &[Action]) -> Option<QuitMode> {
         }
     })
 }
+
+async fn edit_paths(
+    base: &Path,
+    paths: &[PathBuf],
+    remote: Option<&Path>,
+) -> Result<Vec<String>, AppError> {
+    let content: Vec<_> = paths
+        .iter()
+        .map(|path| bulkrename::to_line(base, path))
+        .collect();
+
+    // NOTE: the file is created exclusively, only readable by the user and removed on drop
+    let mut file = tempfile::Builder::new()
+        .prefix("yeet-bulkrename-")
+        .tempfile()?;
+
+    file.write_all((content.join("\n") + "\n").as_bytes())?;
+    file.flush()?;
+
+    let status = editor::path(file.path(), remote).await;
+    let content = fs::read_to_string(file.path()).await;
+
+    let status = status?;
+    if !status.success() {
+        return Err(AppError::ExecutionFailed(format!(
+            "editor exited with {}",
+            status
+        )));
+    }
+
+    Ok(content?.lines().map(|line| line.to_owned()).collect())
+}
+
+async fn run_in_foreground(
+    base: &Path,
+    command: &str,
+    remote: Option<&Path>,
+) -> Result<ExitStatus, AppError> {
+    let status = shell::foreground(base, command, remote).await?;
+
+    // NOTE: keeps the output visible until the user returns to yeet
+    let mut stderr = stderr();
+    write!(stderr, "\nPress ENTER to continue")?;
+    stderr.flush()?;
+
+    tokio::task::spawn_blocking(|| stdin().read_line(&mut String::new()))
+        .await
+        .map_err(|err| AppError::ExecutionFailed(err.to_string()))??;
+
+    Ok(status)
+}
@@ -0,0 +1,26 @@
+use std::{env, io, path::Path, process::ExitStatus};
+
+use tokio::process::Command;
+
+use crate::remote;
+
+#[cfg(not(windows))]
+const DEFAULT_EDITOR: &str = "vi";
+
+#[cfg(windows)]
+const DEFAULT_EDITOR: &str = "notepad";
+
+pub async fn path(path: &Path, remote: Option<&Path>) -> Result<ExitStatus, io::Error> {
+    let editor = env::var("EDITOR").unwrap_or_else(|_| DEFAULT_EDITOR.to_owned());
+
+    // NOTE: editors like 'code --wait' are configured with arguments
+    let mut arguments = editor.split_whitespace();
+    let program = arguments.next().unwrap_or(DEFAULT_EDITOR);
+
+    remote::with_socket_env(&mut Command::new(program), remote)
+        .args(arguments)
+        .arg(path)
+        .spawn()?
+        .wait()
+        .await
+}
@@ -12,16 +12,28 @@ pub enum AppError {
     ExecutionFailed(String),
     #[error("File operation failed")]
     FileOperationFailed(#[from] std::io::Error),
+    #[error("Conflict policy {0} is invalid")]
+    InvalidConflictPolicy(String),
     #[error("Invalid mime type resolved")]
     InvalidMimeType,
+    #[error("Settings option {0} is invalid")]
+    InvalidSettingsOption(String),
+    #[error("Sort mode {0} is invalid")]
+    InvalidSortMode(String),
     #[error("Path target is invalid")]
     InvalidTargetPath,
+    #[error("Trash backend {0} is invalid")]
+    InvalidTrashBackend(String),
     #[error("Loading navigation history failed")]
     LoadHistoryFailed,
+    #[error("Loading keymap failed: {0}")]
+    LoadKeymapFailed(#[from] yeet_keymap::KeyMapError),
     #[error("Loading marks failed")]
     LoadMarkFailed,
     #[error("Loading quickfix failed")]
     LoadQuickFixFailed,
+    #[error("Loading settings failed: {0}")]
+    LoadSettingsFailed(String),
     #[error("Preview picker is not set")]
     PreviewPickerNotResolved,
     #[error("Generating preview protocol failed")]
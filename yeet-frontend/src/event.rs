@@ -1,6 +1,8 @@
 use std::{
+    fs::Metadata,
     path::{Path, PathBuf},
     sync::Arc,
+    time::SystemTime,
 };
 
 use futures::{FutureExt, StreamExt};
@@ -13,20 +15,22 @@ use tokio::{
     select,
     sync::{
         mpsc::{self, Receiver},
-        Mutex,
+        oneshot, Mutex,
     },
 };
 use tokio_util::sync::CancellationToken;
 use yeet_buffer::model::Mode;
 use yeet_keymap::{
     conversion,
-    message::{KeySequence, KeymapMessage},
-    MessageResolver,
+    message::{KeySequence, KeymapMessage, PrintContent},
+    KeyMapError, MessageResolver,
 };
 
 use crate::{
     error::AppError,
-    init::junkyard::get_junkyard_path,
+    init::{junkyard::get_junkyard_path, trash},
+    model::{gitignore::GitIgnores, qfix::QuickFixEntry},
+    remote::{self, RemoteState},
     task::{Task, TaskManager},
 };
 
@@ -54,23 +58,38 @@ impl Envelope {
 #[derive(Debug, Eq, PartialEq)]
 pub enum MessageSource {
     Filesystem,
+    Remote,
     Task,
     User,
 }
 
 pub enum Message {
     Keymap(KeymapMessage),
-    EnumerationChanged(PathBuf, Vec<(ContentKind, String)>, Option<String>),
-    EnumerationFinished(PathBuf, Vec<(ContentKind, String)>, Option<String>),
+    AddToQuickFix(Vec<PathBuf>),
+    BulkRenameEdited(PathBuf, Vec<PathBuf>, Vec<String>),
+    ConflictDetected(Task),
+    EnumerationChanged(PathBuf, Vec<DirectoryContent>, Option<String>),
+    EnumerationFinished(
+        PathBuf,
+        Vec<DirectoryContent>,
+        Option<String>,
+        Option<GitIgnores>,
+    ),
     Error(String),
-    FdResult(Vec<PathBuf>),
+    FdResult(String, Vec<PathBuf>),
+    JunkYardEntryMeasured(PathBuf, u64),
     PathRemoved(PathBuf),
     PathsAdded(Vec<PathBuf>),
     PreviewLoaded(Preview),
+    QuickFixImported(String, Vec<QuickFixEntry>),
     Rerender,
     Resize(u16, u16),
+    RgResult(String, Vec<QuickFixEntry>),
+    ShellFinished(Vec<PrintContent>),
+    StateRequested(oneshot::Sender<RemoteState>),
     TaskStarted(String, CancellationToken),
     TaskEnded(String),
+    TaskProgress(String, TaskProgress),
     ZoxideResult(PathBuf),
 }
 
@@ -78,26 +97,92 @@ impl std::fmt::Debug for Message {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Message::Keymap(msg) => write!(f, "Keymap({:?})", msg),
+            Message::AddToQuickFix(paths) => write!(f, "AddToQuickFix({:?})", paths),
+            Message::BulkRenameEdited(base, paths, _) => {
+                write!(f, "BulkRenameEdited({:?}, {:?}, _)", base, paths)
+            }
+            Message::ConflictDetected(task) => write!(f, "ConflictDetected({:?})", task),
             Message::EnumerationChanged(path, _, opt) => {
                 write!(f, "EnumerationChanged({:?}, _, {:?})", path, opt)
             }
-            Message::EnumerationFinished(path, _, opt) => {
+            Message::EnumerationFinished(path, _, opt, _) => {
                 write!(f, "EnumerationFinished({:?}, {:?})", path, opt)
             }
             Message::Error(err) => write!(f, "Error({:?})", err),
-            Message::FdResult(paths) => write!(f, "FdResult({:?})", paths),
+            Message::FdResult(title, paths) => write!(f, "FdResult({:?}, {:?})", title, paths),
+            Message::JunkYardEntryMeasured(path, size) => {
+                write!(f, "JunkYardEntryMeasured({:?}, {})", path, size)
+            }
             Message::PathRemoved(path) => write!(f, "PathRemoved({:?})", path),
             Message::PathsAdded(paths) => write!(f, "PathsAdded({:?})", paths),
             Message::PreviewLoaded(preview) => write!(f, "PreviewLoaded({:?})", preview),
+            Message::QuickFixImported(title, entries) => {
+                write!(f, "QuickFixImported({:?}, {:?})", title, entries)
+            }
             Message::Rerender => write!(f, "Rerender"),
             Message::Resize(x, y) => write!(f, "Resize({}, {})", x, y),
+            Message::RgResult(title, entries) => {
+                write!(f, "RgResult({:?}, {:?})", title, entries)
+            }
+            Message::ShellFinished(content) => write!(f, "ShellFinished({:?})", content),
+            Message::StateRequested(_) => write!(f, "StateRequested"),
             Message::TaskStarted(identifier, _) => write!(f, "TaskStarted({})", identifier),
             Message::TaskEnded(identifier) => write!(f, "TaskEnded({})", identifier),
+            Message::TaskProgress(identifier, progress) => {
+                write!(f, "TaskProgress({}, {:?})", identifier, progress)
+            }
             Message::ZoxideResult(path) => write!(f, "ZoxideResult({:?})", path),
         }
     }
 }
 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TaskProgress {
+    pub bytes: u64,
+    pub entries: usize,
+}
+
+impl TaskProgress {
+    pub fn to_compact_string(&self) -> String {
+        if self.bytes > 0 {
+            to_human_readable_size(self.bytes)
+        } else {
+            format!("{} entries", self.entries)
+        }
+    }
+}
+
+impl std::fmt::Display for TaskProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} entries, {}",
+            self.entries,
+            to_human_readable_size(self.bytes)
+        )
+    }
+}
+
+pub fn to_human_readable_size(bytes: u64) -> String {
+    let units = ["B", "K", "M", "G", "T"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    // NOTE: keeps sizes within five characters to align size columns
+    if unit == 0 {
+        format!("{}{}", bytes, units[unit])
+    } else if size < 10.0 {
+        format!("{:.1}{}", size, units[unit])
+    } else {
+        format!("{:.0}{}", size, units[unit])
+    }
+}
+
 pub enum Preview {
     Content(PathBuf, Vec<String>),
     Image(PathBuf, Protocol),
@@ -132,12 +217,76 @@ impl PartialEq for Preview {
 pub enum ContentKind {
     Directory,
     File,
+    Symlink { is_broken: bool, target: PathBuf },
+}
+
+impl ContentKind {
+    pub fn new(path: &Path) -> Self {
+        match std::fs::read_link(path) {
+            Ok(target) => ContentKind::Symlink {
+                is_broken: !path.exists(),
+                target,
+            },
+            Err(_) if path.is_dir() => ContentKind::Directory,
+            Err(_) => ContentKind::File,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DirectoryContent {
+    pub is_gitignored: bool,
+    pub kind: ContentKind,
+    pub modified: Option<SystemTime>,
+    pub name: String,
+    pub permissions: Option<u32>,
+    pub size: u64,
+    pub uid: Option<u32>,
+}
+
+impl DirectoryContent {
+    pub fn new(name: String, kind: ContentKind, metadata: Option<&Metadata>) -> Self {
+        Self {
+            is_gitignored: false,
+            kind,
+            modified: metadata.and_then(|m| m.modified().ok()),
+            name,
+            permissions: metadata.and_then(get_permissions),
+            size: metadata.map_or(0, |m| m.len()),
+            uid: metadata.and_then(get_uid),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn get_uid(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(metadata.uid())
+}
+
+#[cfg(not(unix))]
+fn get_uid(_metadata: &Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn get_permissions(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    Some(metadata.permissions().mode())
+}
+
+#[cfg(not(unix))]
+fn get_permissions(_metadata: &Metadata) -> Option<u32> {
+    None
 }
 
 pub struct Emitter {
     cancellation: CancellationToken,
     crossterm_cancellation: CancellationToken,
     pub receiver: Receiver<Envelope>,
+    pub remote: Option<PathBuf>,
     resolver: Arc<Mutex<MessageResolver>>,
     sender: mpsc::Sender<Envelope>,
     tasks: TaskManager,
@@ -159,8 +308,26 @@ impl Emitter {
 
         let resolver = Arc::new(Mutex::new(MessageResolver::default()));
 
+        let remote = remote::get_socket_path().and_then(|path| {
+            remote::start(&path, sender.clone(), cancellation.child_token())?;
+            Ok(path)
+        });
+
+        let remote = match remote {
+            Ok(path) => Some(path),
+            Err(error) => {
+                tracing::error!("starting remote failed: {:?}", error);
+                None
+            }
+        };
+
         let (task_sender, mut task_receiver) = mpsc::channel(1);
-        let tasks = TaskManager::new(task_sender, resolver.clone(), cancellation.child_token());
+        let tasks = TaskManager::new(
+            task_sender,
+            resolver.clone(),
+            remote.clone(),
+            cancellation.child_token(),
+        );
 
         tokio::spawn(async move {
             loop {
@@ -199,6 +366,7 @@ impl Emitter {
             sender,
             tasks,
             receiver,
+            remote,
             resolver,
             watcher,
         }
@@ -220,6 +388,15 @@ impl Emitter {
 
     pub fn shutdown(&mut self) {
         self.cancellation.cancel();
+
+        if let Some(path) = &self.remote {
+            remote::stop(path);
+        }
+    }
+
+    pub async fn add_user_mappings(&mut self, content: &str) -> Vec<KeyMapError> {
+        let mut resolver = self.resolver.lock().await;
+        resolver.add_user_mappings(content)
     }
 
     pub async fn set_current_mode(&mut self, mode: Mode) {
@@ -228,7 +405,9 @@ impl Emitter {
     }
 
     pub fn unwatch(&mut self, path: &Path) -> Result<(), AppError> {
-        if path != get_junkyard_path()? {
+        // NOTE: the junk yard and trash are watched for the whole session
+        let trash_path = trash::get_trash_path(dirs::data_dir())?.join("files");
+        if !path.starts_with(get_junkyard_path()?) && path != trash_path {
             Ok(self.watcher.unwatch(path)?)
         } else {
             Ok(())
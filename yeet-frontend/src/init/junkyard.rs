@@ -1,19 +1,28 @@
 use std::{
-    fs::File,
-    path::{Path, PathBuf},
-    time,
+    fs::{self as std_fs, File},
+    io::ErrorKind,
+    path::{Component, Path, PathBuf},
 };
 
 use flate2::{read::GzDecoder, write::GzEncoder, Compression};
-use tar::Archive;
+use tar::{Archive, EntryType, Header};
 use tokio::fs;
+use tokio_util::sync::CancellationToken;
+
+use super::trash;
 
 use crate::{
     error::AppError,
     event::Emitter,
-    model::junkyard::{FileEntry, JunkYard},
-    task::Task,
-    update::junkyard::add_or_update_junkyard_entry,
+    model::junkyard::{FileEntry, FileEntryFormat, JunkYard, TrashBackend},
+    settings::Settings,
+    task::{
+        progress::{ProgressRead, ProgressReporter},
+        Task,
+    },
+    update::junkyard::{
+        add_or_update_junkyard_entry, evict_from_junkyard, get_uncompressed_path, is_junkyard_path,
+    },
 };
 
 pub fn get_junkyard_path() -> Result<PathBuf, AppError> {
@@ -25,105 +34,288 @@ pub fn get_junkyard_path() -> Result<PathBuf, AppError> {
     Ok(yard_dir)
 }
 
-pub async fn cache_and_compress(entry: FileEntry) -> Result<(), AppError> {
-    let cache_path = get_junk_cache_path().await?;
+pub async fn trash(entry: FileEntry, reporter: &mut ProgressReporter) -> Result<(), AppError> {
+    if let Some(parent) = entry.cache.parent() {
+        fs::create_dir_all(parent).await?;
+    }
 
-    let added_at = match time::SystemTime::now().duration_since(time::UNIX_EPOCH) {
-        Ok(time) => time.as_nanos(),
-        Err(_) => 0,
-    };
+    match fs::rename(&entry.target, &entry.cache).await {
+        Ok(()) => Ok(()),
+        // NOTE: renaming fails across file systems, thus the origin gets compressed instead
+        Err(error) if error.kind() == ErrorKind::CrossesDevices => {
+            tracing::debug!("moving into junk yard failed, compressing: {:?}", error);
 
-    let target_path = cache_path.join(format!("{}/", added_at));
-    if !target_path.exists() {
-        fs::create_dir_all(&target_path).await?;
+            compress_to_junkyard(&entry, &entry.target, reporter).await?;
+            remove_path(&entry.target).await
+        }
+        Err(error) => Err(error.into()),
     }
+}
 
-    if let Some(file_name) = entry.target.file_name() {
-        let target_file = target_path.join(file_name);
-        fs::rename(entry.target, target_file.clone()).await?;
-        compress_with_archive_name(&target_file, &entry.id).await?;
-    }
+pub async fn compress(entry: FileEntry, reporter: &mut ProgressReporter) -> Result<(), AppError> {
+    compress_to_junkyard(&entry, &entry.target, reporter).await
+}
 
-    fs::remove_dir_all(target_path).await?;
+pub async fn compress_uncompressed(
+    entry: FileEntry,
+    reporter: &mut ProgressReporter,
+) -> Result<(), AppError> {
+    // NOTE: the entry is missing if trashing failed or already fell back to compression
+    if entry.format != FileEntryFormat::Uncompressed || entry.cache.symlink_metadata().is_err() {
+        return Ok(());
+    }
 
-    Ok(())
+    compress_to_junkyard(&entry, &entry.cache, reporter).await?;
+    remove_path(&entry.cache).await
 }
 
-pub async fn compress(entry: FileEntry) -> Result<(), AppError> {
-    compress_with_archive_name(&entry.target, &entry.id).await
+pub async fn delete(entry: FileEntry) -> Result<(), AppError> {
+    remove_path(&entry.cache).await
 }
 
-pub async fn delete(entry: FileEntry) -> Result<(), AppError> {
-    let path = get_junk_path().await?.join(&entry.id);
-    fs::remove_file(path).await?;
-    Ok(())
+pub async fn get_size(path: &Path, cancellation: &CancellationToken) -> u64 {
+    let mut size = 0;
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(path) = pending.pop() {
+        if cancellation.is_cancelled() {
+            break;
+        }
+
+        let metadata = match fs::symlink_metadata(&path).await {
+            Ok(it) => it,
+            Err(_) => continue,
+        };
+
+        if metadata.is_dir() {
+            if let Ok(mut read_dir) = fs::read_dir(&path).await {
+                while let Ok(Some(child)) = read_dir.next_entry().await {
+                    pending.push(child.path());
+                }
+            }
+        } else {
+            size += metadata.len();
+        }
+    }
+
+    size
 }
 
-pub async fn init_junkyard(junk: &mut JunkYard, emitter: &mut Emitter) -> Result<(), AppError> {
+pub async fn init_junkyard(
+    junk: &mut JunkYard,
+    settings: &Settings,
+    emitter: &mut Emitter,
+) -> Result<(), AppError> {
     junk.path = get_junk_path().await?;
 
-    let mut read_dir = fs::read_dir(&junk.path).await?;
-    while let Some(entry) = read_dir.next_entry().await? {
-        if let Some(obsolete) = add_or_update_junkyard_entry(junk, &entry.path()) {
-            for entry in obsolete.entries {
-                emitter.run(Task::DeleteJunkYardEntry(entry));
+    let uncompressed_path = get_uncompressed_path(junk);
+    if !uncompressed_path.exists() {
+        fs::create_dir_all(&uncompressed_path).await?;
+    }
+
+    for path in [&junk.path.clone(), &uncompressed_path] {
+        let mut read_dir = fs::read_dir(path).await?;
+        while let Some(entry) = read_dir.next_entry().await? {
+            add_or_update_junkyard_entry(junk, &entry.path());
+        }
+    }
+
+    // NOTE: an existing trash is loaded regardless of the backend to keep former trashes
+    let trash_path = trash::get_trash_path(dirs::data_dir())?.join("files");
+    if settings.trash_backend == TrashBackend::Freedesktop && !trash_path.exists() {
+        fs::create_dir_all(&trash_path).await?;
+    }
+
+    if trash_path.exists() {
+        junk.trash_path = trash_path;
+
+        let mut read_dir = fs::read_dir(&junk.trash_path).await?;
+        while let Some(entry) = read_dir.next_entry().await? {
+            if is_junkyard_path(junk, &entry.path()) {
+                add_or_update_junkyard_entry(junk, &entry.path());
             }
         }
+
+        emitter.watch(&junk.trash_path)?;
+    }
+
+    for transaction in evict_from_junkyard(junk, &settings.junkyard) {
+        for entry in transaction.entries {
+            emitter.run(Task::DeleteJunkYardEntry(entry));
+        }
+    }
+
+    let entries = junk
+        .yanked
+        .iter()
+        .chain(junk.trashed.iter())
+        .flat_map(|transaction| transaction.entries.iter());
+
+    for entry in entries {
+        emitter.run(Task::MeasureJunkYardEntry(entry.cache.clone()));
     }
 
     emitter.watch(&junk.path)?;
+    emitter.watch(&uncompressed_path)?;
 
     Ok(())
 }
 
-pub fn restore(entry: FileEntry, path: PathBuf) -> Result<(), AppError> {
+pub fn list(entry: &FileEntry) -> Result<Vec<String>, AppError> {
+    match entry.format {
+        FileEntryFormat::Compressed => list_archive(entry),
+        FileEntryFormat::Uncompressed => list_directory(entry),
+    }
+}
+
+fn list_archive(entry: &FileEntry) -> Result<Vec<String>, AppError> {
+    let archive_file = File::open(&entry.cache)?;
+    let archive_decoder = GzDecoder::new(archive_file);
+    let mut archive = Archive::new(archive_decoder);
+
+    let mut lines = Vec::new();
+    for archive_entry in archive.entries()? {
+        let archive_entry = archive_entry?;
+        let header = archive_entry.header();
+        let path = archive_entry.path()?.to_string_lossy().to_string();
+
+        let line = match header.entry_type() {
+            EntryType::Directory => format!("{:>10} {}", "-", path),
+            EntryType::Symlink => match archive_entry.link_name()? {
+                Some(link) => format!("{:>10} {} -> {}", "-", path, link.display()),
+                None => format!("{:>10} {}", "-", path),
+            },
+            _ => format!("{:>10} {}", archive_entry.size(), path),
+        };
+
+        lines.push(line);
+    }
+
+    Ok(lines)
+}
+
+fn list_directory(entry: &FileEntry) -> Result<Vec<String>, AppError> {
+    let name = match entry.target.file_name() {
+        Some(it) => PathBuf::from(it),
+        None => return Err(AppError::InvalidTargetPath),
+    };
+
+    let mut lines = Vec::new();
+    let mut pending = vec![(entry.cache.clone(), name)];
+    while let Some((path, name)) = pending.pop() {
+        let metadata = std_fs::symlink_metadata(&path)?;
+        let file_type = metadata.file_type();
+
+        let line = if file_type.is_symlink() {
+            let link = std_fs::read_link(&path)?;
+            format!("{:>10} {} -> {}", "-", name.display(), link.display())
+        } else if file_type.is_dir() {
+            let mut children = std_fs::read_dir(&path)?
+                .map(|child| child.map(|child| child.file_name()))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            children.sort_unstable_by(|a, b| b.cmp(a));
+            for child in children {
+                pending.push((path.join(&child), name.join(&child)));
+            }
+
+            format!("{:>10} {}/", "-", name.display())
+        } else {
+            format!("{:>10} {}", metadata.len(), name.display())
+        };
+
+        lines.push(line);
+    }
+
+    Ok(lines)
+}
+
+pub fn restore(
+    entry: FileEntry,
+    target: &Path,
+    reporter: &mut ProgressReporter,
+) -> Result<(), AppError> {
     let archive_file = File::open(entry.cache)?;
     let archive_decoder = GzDecoder::new(archive_file);
     let mut archive = Archive::new(archive_decoder);
-    archive.unpack(path)?;
+
+    // NOTE: directories are unpacked last to prevent permissions blocking their content
+    let mut directories = Vec::new();
+    for archive_entry in archive.entries()? {
+        let mut archive_entry = archive_entry?;
+
+        // NOTE: the archive root is replaced with target to restore with a resolved name
+        let path = archive_entry.path()?;
+        if path.components().any(|c| c == Component::ParentDir) {
+            return Err(AppError::InvalidTargetPath);
+        }
+
+        let destination = target.join(path.components().skip(1).collect::<PathBuf>());
+        if archive_entry.header().entry_type() == EntryType::Directory {
+            directories.push((archive_entry, destination));
+        } else {
+            if let Some(parent) = destination.parent() {
+                std_fs::create_dir_all(parent)?;
+            }
+
+            let size = archive_entry.size();
+            archive_entry.unpack(&destination)?;
+            reporter.add(1, size);
+        }
+    }
+
+    for (mut directory, destination) in directories {
+        directory.unpack(&destination)?;
+        reporter.add(1, 0);
+    }
 
     Ok(())
 }
 
-async fn compress_with_archive_name(path: &Path, archive_name: &str) -> Result<(), AppError> {
-    let compress_path = get_junk_compress_path().await?.join(archive_name);
+async fn compress_to_junkyard(
+    entry: &FileEntry,
+    path: &Path,
+    reporter: &mut ProgressReporter,
+) -> Result<(), AppError> {
+    let compress_path = get_junk_compress_path().await?.join(&entry.id);
 
     let file = File::create(&compress_path)?;
     let encoder = GzEncoder::new(file, Compression::default());
     let mut archive = tar::Builder::new(encoder);
+    archive.follow_symlinks(false);
 
-    if let Some(file_name) = path.file_name() {
-        if path.is_dir() {
-            let archive_directory = format!("{}/", file_name.to_string_lossy());
-            archive.append_dir_all(archive_directory, path)?;
-        } else {
-            archive.append_file(
-                file_name.to_string_lossy().to_string(),
-                &mut File::open(path)?,
-            )?;
+    // NOTE: the archive root is named after the target, because uncompressed entries are renamed
+    if let Some(file_name) = entry.target.file_name() {
+        let mut pending = vec![(path.to_path_buf(), PathBuf::from(file_name))];
+        while let Some((source, name)) = pending.pop() {
+            let metadata = std_fs::symlink_metadata(&source)?;
+            if metadata.is_dir() {
+                for entry in std_fs::read_dir(&source)? {
+                    let entry = entry?;
+                    pending.push((entry.path(), name.join(entry.file_name())));
+                }
+            }
+
+            if metadata.is_file() {
+                let mut header = Header::new_gnu();
+                header.set_metadata(&metadata);
+
+                let reader = ProgressRead::new(File::open(&source)?, reporter);
+                archive.append_data(&mut header, &name, reader)?;
+            } else {
+                archive.append_path_with_name(&source, &name)?;
+            }
+
+            reporter.add(1, 0);
         }
     }
     archive.finish()?;
 
-    let target_path = get_junk_path().await?.join(archive_name);
-    match fs::rename(compress_path, target_path).await {
-        Ok(it) => it,
-        Err(err) => {
-            tracing::error!("Failed to rename file: {:?}", err);
-        }
-    };
+    let target_path = get_junk_path().await?.join(&entry.id);
+    fs::rename(compress_path, target_path).await?;
 
     Ok(())
 }
 
-async fn get_junk_cache_path() -> Result<PathBuf, AppError> {
-    let path = get_junk_path().await?.join(".cache/");
-    if !path.exists() {
-        fs::create_dir_all(&path).await?;
-    }
-    Ok(path)
-}
-
 async fn get_junk_compress_path() -> Result<PathBuf, AppError> {
     let path = get_junk_path().await?.join(".compress/");
     if !path.exists() {
@@ -139,3 +331,14 @@ async fn get_junk_path() -> Result<PathBuf, AppError> {
     }
     Ok(junk_path)
 }
+
+async fn remove_path(path: &Path) -> Result<(), AppError> {
+    let metadata = fs::symlink_metadata(path).await?;
+    if metadata.is_dir() {
+        fs::remove_dir_all(path).await?;
+    } else {
+        fs::remove_file(path).await?;
+    }
+
+    Ok(())
+}
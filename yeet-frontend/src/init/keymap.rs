@@ -0,0 +1,39 @@
+use std::{fs, path::Path};
+
+use crate::{error::AppError, event::Emitter};
+
+#[tracing::instrument(skip(emitter))]
+pub async fn load_keymap_from_file(emitter: &mut Emitter) -> Result<(), AppError> {
+    let keymap_path = get_keymap_path()?;
+    if !Path::new(&keymap_path).exists() {
+        tracing::debug!("keymap file does not exist on path {}", keymap_path);
+
+        return Ok(());
+    }
+
+    // TODO: change to tokio fs
+    let content = fs::read_to_string(keymap_path)?;
+    let errors = emitter.add_user_mappings(&content).await;
+
+    tracing::trace!("keymap file read");
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Aggregate(
+            errors.into_iter().map(AppError::LoadKeymapFailed).collect(),
+        ))
+    }
+}
+
+fn get_keymap_path() -> Result<String, AppError> {
+    let config_dir = match dirs::config_dir() {
+        Some(config_dir) => match config_dir.to_str() {
+            Some(config_dir_string) => config_dir_string.to_string(),
+            None => return Err(AppError::InvalidTargetPath),
+        },
+        None => return Err(AppError::InvalidTargetPath),
+    };
+
+    Ok(format!("{}{}", config_dir, "/yeet/keymap.toml"))
+}
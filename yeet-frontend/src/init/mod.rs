@@ -1,4 +1,6 @@
 pub mod history;
 pub mod junkyard;
+pub mod keymap;
 pub mod mark;
 pub mod qfix;
+pub mod trash;
@@ -3,7 +3,10 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::{error::AppError, model::qfix::QuickFix};
+use crate::{
+    error::AppError,
+    model::qfix::{QuickFix, QuickFixEntry, QuickFixList, QuickFixLocation},
+};
 
 #[tracing::instrument]
 pub fn load_qfix_from_files(qfix: &mut QuickFix) -> Result<(), AppError> {
@@ -18,22 +21,53 @@ pub fn load_qfix_from_files(qfix: &mut QuickFix) -> Result<(), AppError> {
     let qfix_cache_file = File::open(qfix_cache_path)?;
     let mut qfix_entry_csv_reader = csv::ReaderBuilder::new()
         .has_headers(false)
+        .flexible(true)
         .from_reader(qfix_cache_file);
 
     tracing::trace!("qfix file opened for reading");
 
+    let mut lists: Vec<QuickFixList> = Vec::new();
+    let mut current_list = None;
     for result in qfix_entry_csv_reader.records() {
         let record = match result {
             Ok(record) => record,
             Err(_) => return Err(AppError::LoadQuickFixFailed),
         };
 
-        let path = match record.get(0) {
-            Some(path) => PathBuf::from(path),
-            None => continue,
+        // NOTE: single column records are paths written before lists were introduced
+        let fields: Vec<_> = record.iter().collect();
+        let entry = match fields.as_slice() {
+            ["list", title, current] => {
+                if *current == "*" {
+                    current_list = Some(lists.len());
+                }
+                lists.push(QuickFixList::new(title));
+                continue;
+            }
+            ["entry", path] | [path] => QuickFixEntry::from(PathBuf::from(path)),
+            ["entry", path, line, column, text] => QuickFixEntry {
+                path: PathBuf::from(path),
+                location: Some(QuickFixLocation {
+                    line: line.parse().map_err(|_| AppError::LoadQuickFixFailed)?,
+                    column: column.parse().map_err(|_| AppError::LoadQuickFixFailed)?,
+                    text: text.to_string(),
+                }),
+            },
+            _ => return Err(AppError::LoadQuickFixFailed),
         };
 
-        qfix.entries.push(path);
+        if lists.is_empty() {
+            lists.push(QuickFixList::new("manual"));
+        }
+
+        if let Some(list) = lists.last_mut() {
+            list.entries.push(entry);
+        }
+    }
+
+    if !lists.is_empty() {
+        qfix.current_list = current_list.unwrap_or(lists.len() - 1);
+        qfix.lists = lists;
     }
 
     tracing::trace!("qfix file read");
@@ -59,16 +93,36 @@ pub fn save_qfix_to_files(qfix: &QuickFix) -> Result<(), AppError> {
 
     tracing::trace!("qfix file opened for writing");
 
-    let mut writer = csv::Writer::from_writer(qfix_entry_writer);
-    for path in qfix.entries.iter() {
-        if !path.exists() {
-            continue;
+    let mut writer = csv::WriterBuilder::new()
+        .flexible(true)
+        .from_writer(qfix_entry_writer);
+
+    for (index, list) in qfix.lists.iter().enumerate() {
+        let current = if index == qfix.current_list { "*" } else { "" };
+        if let Err(error) = writer.write_record(["list", &list.title, current]) {
+            tracing::error!("writing qfix list failed: {:?}", error);
         }
 
-        if let Some(path) = path.to_str() {
-            let write_result = writer.write_record([path]);
-            if let Err(error) = write_result {
-                tracing::error!("writing qfix entry failed: {:?}", error);
+        for entry in list.entries.iter() {
+            if !entry.path.exists() {
+                continue;
+            }
+
+            if let Some(path) = entry.path.to_str() {
+                let write_result = match &entry.location {
+                    Some(location) => writer.write_record([
+                        "entry",
+                        path,
+                        &location.line.to_string(),
+                        &location.column.to_string(),
+                        &location.text,
+                    ]),
+                    None => writer.write_record(["entry", path]),
+                };
+
+                if let Err(error) = write_result {
+                    tracing::error!("writing qfix entry failed: {:?}", error);
+                }
             }
         }
     }
@@ -91,3 +145,114 @@ fn get_qfix_cache_path() -> Result<String, AppError> {
 
     Ok(format!("{}{}", cache_dir, "/yeet/qfix"))
 }
+
+pub fn to_exchange_content(entries: &[QuickFixEntry], nul_separated: bool) -> String {
+    let mut content = String::new();
+    for entry in entries {
+        let path = entry.path.to_string_lossy();
+        if nul_separated {
+            content.push_str(&path);
+            content.push('\0');
+            continue;
+        }
+
+        match &entry.location {
+            Some(location) => content.push_str(&format!(
+                "{}:{}:{}:{}",
+                path, location.line, location.column, location.text
+            )),
+            None => content.push_str(&path),
+        }
+        content.push('\n');
+    }
+    content
+}
+
+// NOTE: nul separated content only contains paths, newline separated content is either a path
+// or an errorfile line in the format path:line:col:text
+pub fn from_exchange_content(base: &Path, content: &str) -> Vec<QuickFixEntry> {
+    let nul_separated = content.contains('\0');
+    let separator = if nul_separated { '\0' } else { '\n' };
+
+    let mut entries: Vec<QuickFixEntry> = Vec::new();
+    for item in content.split(separator) {
+        let item = item.trim_end_matches('\r');
+        if item.trim().is_empty() {
+            continue;
+        }
+
+        let mut entry = if nul_separated {
+            QuickFixEntry::from(PathBuf::from(item))
+        } else {
+            from_errorfile_line(item).unwrap_or_else(|| QuickFixEntry::from(PathBuf::from(item)))
+        };
+        entry.path = base.join(&entry.path);
+
+        if !entries.contains(&entry) {
+            entries.push(entry);
+        }
+    }
+    entries
+}
+
+fn from_errorfile_line(line: &str) -> Option<QuickFixEntry> {
+    for (index, _) in line.match_indices(':') {
+        let mut parts = line[index + 1..].splitn(3, ':');
+        let (row, column, text) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(row), Some(column), Some(text)) => (row, column, text),
+            _ => return None,
+        };
+
+        if let (Ok(row), Ok(column)) = (row.parse(), column.parse()) {
+            return Some(QuickFixEntry {
+                path: PathBuf::from(&line[..index]),
+                location: Some(QuickFixLocation {
+                    line: row,
+                    column,
+                    text: text.to_owned(),
+                }),
+            });
+        }
+    }
+    None
+}
+
+mod test {
+    #[test]
+    fn exchange_content_roundtrip() {
+        use std::path::{Path, PathBuf};
+
+        use crate::model::qfix::{QuickFixEntry, QuickFixLocation};
+
+        let entries = vec![
+            QuickFixEntry::from(PathBuf::from("/a b")),
+            QuickFixEntry {
+                path: PathBuf::from("/c:d/main.rs"),
+                location: Some(QuickFixLocation {
+                    line: 3,
+                    column: 4,
+                    text: "let x: u8 = 1;".to_owned(),
+                }),
+            },
+        ];
+
+        let content = super::to_exchange_content(&entries, false);
+        assert_eq!("/a b\n/c:d/main.rs:3:4:let x: u8 = 1;\n", content);
+        assert_eq!(
+            entries,
+            super::from_exchange_content(Path::new("/base"), &content)
+        );
+
+        let content = super::to_exchange_content(&entries, true);
+        assert_eq!("/a b\0/c:d/main.rs\0", content);
+
+        let imported = super::from_exchange_content(Path::new("/base"), "src\0/abs\0src\0");
+        assert_eq!(
+            vec![
+                QuickFixEntry::from(PathBuf::from("/base/src")),
+                QuickFixEntry::from(PathBuf::from("/abs")),
+            ],
+            imported
+        );
+    }
+}
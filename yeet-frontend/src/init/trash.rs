@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use tokio::{fs, io::AsyncWriteExt};
+
+use crate::{error::AppError, model::junkyard::FileEntry};
+
+pub fn get_trash_path(data_dir: Option<PathBuf>) -> Result<PathBuf, AppError> {
+    match data_dir {
+        Some(data_dir) => Ok(data_dir.join("Trash")),
+        None => Err(AppError::InvalidTargetPath),
+    }
+}
+
+pub async fn trash(entry: &FileEntry) -> Result<(), AppError> {
+    let info_path = get_info_path(&entry.cache)?;
+    if let Some(parent) = info_path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    if let Some(parent) = entry.cache.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    let content = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        encode_path(&entry.target),
+        Local::now().format("%Y-%m-%dT%H:%M:%S")
+    );
+
+    // NOTE: the spec demands the info file to exist before the file is moved into the trash
+    let mut info = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&info_path)
+        .await?;
+
+    info.write_all(content.as_bytes()).await?;
+    info.flush().await?;
+
+    // NOTE: moving across file systems is not supported, the file stays untouched in this case
+    if let Err(error) = fs::rename(&entry.target, &entry.cache).await {
+        fs::remove_file(&info_path).await?;
+        return Err(error.into());
+    }
+
+    Ok(())
+}
+
+pub async fn delete(entry: &FileEntry) -> Result<(), AppError> {
+    let metadata = fs::symlink_metadata(&entry.cache).await?;
+    if metadata.is_dir() {
+        fs::remove_dir_all(&entry.cache).await?;
+    } else {
+        fs::remove_file(&entry.cache).await?;
+    }
+
+    let info_path = get_info_path(&entry.cache)?;
+    if info_path.exists() {
+        fs::remove_file(info_path).await?;
+    }
+
+    Ok(())
+}
+
+fn get_info_path(cache: &Path) -> Result<PathBuf, AppError> {
+    let (files, name) = match (cache.parent(), cache.file_name()) {
+        (Some(files), Some(name)) => (files, name),
+        _ => return Err(AppError::InvalidTargetPath),
+    };
+
+    let trash = match files.parent() {
+        Some(it) => it,
+        None => return Err(AppError::InvalidTargetPath),
+    };
+
+    let mut info_name = name.to_os_string();
+    info_name.push(".trashinfo");
+
+    Ok(trash.join("info").join(info_name))
+}
+
+fn encode_path(path: &Path) -> String {
+    let mut encoded = String::new();
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    encoded
+}
+
+mod test {
+    #[tokio::test]
+    async fn trash_list_and_delete() {
+        use std::fs;
+
+        use crate::model::junkyard::{FileEntry, FileEntryFormat, FileEntryStatus, TrashBackend};
+
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let base = directory.path();
+        let source = base.join("src").join("my file");
+        fs::create_dir_all(&source).expect("create source");
+        fs::write(source.join("inner"), "content").expect("write inner");
+
+        let id = "1708576379595%my file".to_owned();
+        let entry = FileEntry {
+            backend: TrashBackend::Freedesktop,
+            cache: base.join("data").join("Trash").join("files").join(&id),
+            format: FileEntryFormat::Uncompressed,
+            id: id.clone(),
+            size: None,
+            status: FileEntryStatus::Processing,
+            target: source.clone(),
+        };
+
+        assert!(super::trash(&entry).await.is_ok());
+        assert!(!source.exists());
+        assert!(entry.cache.join("inner").exists());
+
+        let info_path = base
+            .join("data")
+            .join("Trash")
+            .join("info")
+            .join(format!("{}.trashinfo", id));
+
+        let info = fs::read_to_string(&info_path).expect("read trashinfo");
+        assert!(info.starts_with("[Trash Info]\n"));
+        assert!(info.contains(&format!("Path={}/src/my%20file\n", base.to_string_lossy())));
+        assert!(info.contains("DeletionDate="));
+
+        let lines = crate::init::junkyard::list(&entry).expect("list trashed entry");
+        assert_eq!(2, lines.len());
+        assert!(lines[0].ends_with(" my file/"));
+        assert!(lines[1].ends_with(" my file/inner"));
+
+        assert!(super::trash(&entry).await.is_err());
+
+        assert!(super::delete(&entry).await.is_ok());
+        assert!(!entry.cache.exists());
+        assert!(!info_path.exists());
+    }
+
+    #[test]
+    fn get_trash_path_from_data_dir() {
+        let data_home = std::env::temp_dir().join("yeet-xdg-data");
+
+        let trash = super::get_trash_path(Some(data_home.clone())).expect("trash path");
+        assert_eq!(data_home.join("Trash"), trash);
+
+        assert!(super::get_trash_path(None).is_err());
+    }
+}
@@ -4,8 +4,8 @@ use action::{Action, ActionResult};
 use error::AppError;
 use event::{Emitter, Message, MessageSource};
 use init::{
-    history::load_history_from_file, junkyard::init_junkyard, mark::load_marks_from_file,
-    qfix::load_qfix_from_files,
+    history::load_history_from_file, junkyard::init_junkyard, keymap::load_keymap_from_file,
+    mark::load_marks_from_file, qfix::load_qfix_from_files,
 };
 use layout::{AppLayout, CommandLineLayout};
 use model::{qfix::CdoState, Model};
@@ -20,19 +20,22 @@ use yeet_buffer::{message::BufferMessage, model::Mode};
 use yeet_keymap::message::{KeymapMessage, PrintContent, QuitMode};
 
 mod action;
+mod editor;
 pub mod error;
 mod event;
 mod init;
 mod layout;
 mod model;
 mod open;
+pub mod remote;
 pub mod settings;
+mod shell;
 mod task;
 mod terminal;
 mod update;
 mod view;
 
-pub async fn run(settings: Settings) -> Result<(), AppError> {
+pub async fn run(settings: Settings, mut errors: Vec<AppError>) -> Result<(), AppError> {
     let cancellation = CancellationToken::new();
     let mut terminal = TerminalWrapper::start()?;
     let mut emitter = Emitter::start(cancellation.child_token());
@@ -51,7 +54,25 @@ pub async fn run(settings: Settings) -> Result<(), AppError> {
         ..Default::default()
     };
 
-    init_junkyard(&mut model.junk, &mut emitter).await?;
+    init_junkyard(&mut model.junk, &model.settings, &mut emitter).await?;
+
+    if let Err(error) = load_keymap_from_file(&mut emitter).await {
+        match error {
+            AppError::Aggregate(keymap_errors) => errors.extend(keymap_errors),
+            error => errors.push(error),
+        };
+    }
+
+    if !errors.is_empty() {
+        emitter.run(Task::EmitMessages(vec![Message::Keymap(
+            KeymapMessage::Print(
+                errors
+                    .iter()
+                    .map(|error| PrintContent::Error(error.to_string()))
+                    .collect(),
+            ),
+        )]));
+    }
 
     if load_history_from_file(&mut model.history).is_err() {
         emitter.run(Task::EmitMessages(vec![Message::Keymap(
@@ -258,13 +279,13 @@ fn get_command_from_stack(model: &mut Model, emitter: &Emitter, actions: &[Actio
 
     let (next_state, actions) = match &model.qfix.cdo {
         CdoState::Cnext(command) => (
-            CdoState::Cdo(Some(model.qfix.current_index), command.to_owned()),
+            CdoState::Cdo(Some(model.qfix.current().current_index), command.to_owned()),
             vec![action::emit_keymap(KeymapMessage::ExecuteCommandString(
                 "cn".to_owned(),
             ))],
         ),
         CdoState::Cdo(old_index, command) => {
-            if old_index.is_some_and(|index| index >= model.qfix.current_index) {
+            if old_index.is_some_and(|index| index >= model.qfix.current().current_index) {
                 (CdoState::None, Vec::new())
             } else {
                 (
@@ -287,7 +308,11 @@ fn get_command_from_stack(model: &mut Model, emitter: &Emitter, actions: &[Actio
 
 fn is_message_queueing(action: &Action) -> bool {
     match action {
-        Action::EmitMessages(_) => true,
+        Action::BulkRename(_, _)
+        | Action::EmitMessages(_)
+        | Action::OpenInForeground(_)
+        | Action::OpenWith(_, _)
+        | Action::Shell(_, _) => true,
 
         Action::Load(_, _, _)
         | Action::Open(_)
@@ -0,0 +1,53 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::Deserialize;
+
+use crate::error::AppError;
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(try_from = "String")]
+pub enum ConflictPolicy {
+    // NOTE: not configurable, used for renames within directory buffers
+    Fail,
+    Overwrite,
+    #[default]
+    Prompt,
+    Rename,
+    Skip,
+}
+
+impl FromStr for ConflictPolicy {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "overwrite" => Ok(ConflictPolicy::Overwrite),
+            "prompt" => Ok(ConflictPolicy::Prompt),
+            "rename" => Ok(ConflictPolicy::Rename),
+            "skip" => Ok(ConflictPolicy::Skip),
+            _ => Err(AppError::InvalidConflictPolicy(value.to_owned())),
+        }
+    }
+}
+
+impl TryFrom<String> for ConflictPolicy {
+    type Error = AppError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for ConflictPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let content = match self {
+            ConflictPolicy::Fail => "fail",
+            ConflictPolicy::Overwrite => "overwrite",
+            ConflictPolicy::Prompt => "prompt",
+            ConflictPolicy::Rename => "rename",
+            ConflictPolicy::Skip => "skip",
+        };
+
+        write!(f, "{}", content)
+    }
+}
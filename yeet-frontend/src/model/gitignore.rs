@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use ignore::{gitignore::Gitignore, Match};
+
+pub struct GitIgnores {
+    // NOTE: ordered from the enumerated directory up to the repository root
+    ignores: Vec<Gitignore>,
+}
+
+impl GitIgnores {
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        for ignore in self.ignores.iter() {
+            match ignore.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+}
+
+pub fn load(path: &Path) -> Option<GitIgnores> {
+    let mut ignores = Vec::new();
+    for directory in path.ancestors() {
+        let file = directory.join(".gitignore");
+        if file.is_file() {
+            let (ignore, error) = Gitignore::new(&file);
+            if let Some(error) = error {
+                tracing::warn!("gitignore {:?} contains invalid globs: {:?}", file, error);
+            }
+            ignores.push(ignore);
+        }
+
+        if directory.join(".git").exists() {
+            return Some(GitIgnores { ignores });
+        }
+    }
+
+    None
+}
+
+mod test {
+    #[test]
+    fn is_ignored() {
+        use std::fs;
+
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let base = directory.path();
+        let nested = base.join("nested");
+
+        fs::create_dir_all(base.join(".git")).expect("create git dir");
+        fs::create_dir_all(&nested).expect("create nested");
+        fs::write(base.join(".gitignore"), "target/\n*.log\n").expect("write gitignore");
+        fs::write(nested.join(".gitignore"), "!keep.log\n").expect("write nested gitignore");
+
+        let ignores = super::load(&nested).expect("repository found");
+
+        assert!(ignores.is_ignored(&nested.join("target"), true));
+        assert!(!ignores.is_ignored(&nested.join("target"), false));
+        assert!(ignores.is_ignored(&nested.join("debug.log"), false));
+        assert!(!ignores.is_ignored(&nested.join("keep.log"), false));
+        assert!(!ignores.is_ignored(&nested.join("main.rs"), false));
+    }
+}
@@ -1,13 +1,27 @@
-use std::path::PathBuf;
+use std::{fmt::Display, path::PathBuf, str::FromStr};
+
+use serde::Deserialize;
+use yeet_buffer::model::{viewport::ViewPort, Buffer, Cursor};
+
+use crate::error::AppError;
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct JunkYard {
     pub current: FileEntryType,
     pub path: PathBuf,
+    pub trash_path: PathBuf,
     pub trashed: Vec<FileTransaction>,
     pub yanked: Option<FileTransaction>,
 }
 
+#[derive(Default)]
+pub struct JunkYardBrowser {
+    pub buffer: Buffer,
+    pub cursor: Option<Cursor>,
+    pub entries: Vec<FileEntry>,
+    pub viewport: ViewPort,
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub enum FileEntryType {
     _Custom(String),
@@ -24,15 +38,65 @@ pub struct FileTransaction {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FileEntry {
+    pub backend: TrashBackend,
     pub id: String,
     pub cache: PathBuf,
+    pub format: FileEntryFormat,
+    // NOTE: measured by a task, because walking large trashes would block the ui
+    pub size: Option<u64>,
     pub status: FileEntryStatus,
     pub target: PathBuf,
 }
 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum FileEntryFormat {
+    #[default]
+    Compressed,
+    Uncompressed,
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub enum FileEntryStatus {
     #[default]
     Processing,
     Ready,
 }
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(try_from = "String")]
+pub enum TrashBackend {
+    Freedesktop,
+    #[default]
+    JunkYard,
+}
+
+impl FromStr for TrashBackend {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "freedesktop" => Ok(TrashBackend::Freedesktop),
+            "junkyard" => Ok(TrashBackend::JunkYard),
+            _ => Err(AppError::InvalidTrashBackend(value.to_owned())),
+        }
+    }
+}
+
+impl TryFrom<String> for TrashBackend {
+    type Error = AppError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for TrashBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let content = match self {
+            TrashBackend::Freedesktop => "freedesktop",
+            TrashBackend::JunkYard => "junkyard",
+        };
+
+        write!(f, "{}", content)
+    }
+}
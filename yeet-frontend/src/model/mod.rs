@@ -4,8 +4,10 @@ use std::{
 };
 
 use crate::{
+    event::{DirectoryContent, TaskProgress},
     layout::{AppLayout, CommandLineLayout},
     settings::Settings,
+    task::Task,
 };
 use ratatui::layout::Rect;
 use ratatui_image::protocol::Protocol;
@@ -15,27 +17,43 @@ use yeet_buffer::model::{
     Buffer, Cursor, Mode,
 };
 
-use self::{history::History, junkyard::JunkYard, mark::Marks, qfix::QuickFix, register::Register};
+use self::{
+    gitignore::GitIgnores,
+    history::History,
+    junkyard::{JunkYard, JunkYardBrowser},
+    mark::Marks,
+    qfix::{QuickFix, QuickFixEditor},
+    register::Register,
+    sort::SortMode,
+};
 
+pub mod conflict;
+pub mod gitignore;
 pub mod history;
 pub mod junkyard;
 pub mod mark;
 pub mod qfix;
 pub mod register;
+pub mod sort;
 
 #[derive(Default)]
 pub struct Model {
+    pub bulkrename: Option<Vec<Task>>,
     pub commandline: CommandLine,
+    pub conflicts: Vec<Task>,
     pub current_tasks: HashMap<String, CurrentTask>,
     pub files: FileWindow,
     pub history: History,
     pub junk: JunkYard,
+    pub junk_browser: Option<JunkYardBrowser>,
     pub latest_task_id: u16,
     pub layout: AppLayout,
     pub marks: Marks,
     pub mode: Mode,
     pub mode_before: Option<Mode>,
+    pub owners: HashMap<u32, Option<String>>,
     pub qfix: QuickFix,
+    pub qfix_editor: Option<QuickFixEditor>,
     pub register: Register,
     pub remaining_keysequence: Option<String>,
     pub settings: Settings,
@@ -57,6 +75,7 @@ impl std::fmt::Debug for Model {
 pub struct CurrentTask {
     pub external_id: String,
     pub id: u16,
+    pub progress: Option<TaskProgress>,
     pub token: CancellationToken,
 }
 
@@ -64,6 +83,8 @@ pub struct FileWindow {
     pub current: PathBuffer,
     pub current_vp: ViewPort,
     pub current_cursor: Option<Cursor>,
+    pub enumerations: HashMap<PathBuf, Vec<DirectoryContent>>,
+    pub gitignores: HashMap<PathBuf, Option<GitIgnores>>,
     pub parent: BufferType,
     pub parent_vp: ViewPort,
     pub parent_cursor: Option<Cursor>,
@@ -71,6 +92,7 @@ pub struct FileWindow {
     pub preview_vp: ViewPort,
     pub preview_cursor: Option<Cursor>,
     pub show_border: bool,
+    pub sorts: HashMap<PathBuf, SortMode>,
 }
 
 impl FileWindow {
@@ -123,8 +145,11 @@ impl Default for FileWindow {
             current_vp: ViewPort {
                 line_number: LineNumber::Relative,
                 line_number_width: 3,
+                show_suffix: true,
                 ..Default::default()
             },
+            enumerations: HashMap::new(),
+            gitignores: HashMap::new(),
             parent: Default::default(),
             parent_vp: Default::default(),
             parent_cursor: Default::default(),
@@ -132,6 +157,7 @@ impl Default for FileWindow {
             preview_vp: Default::default(),
             preview_cursor: Default::default(),
             show_border: true,
+            sorts: HashMap::new(),
         }
     }
 }
@@ -192,6 +218,7 @@ impl Default for CommandLine {
 #[derive(Default)]
 pub struct PathBuffer {
     pub buffer: Buffer,
+    pub is_filter_pending: bool,
     pub path: PathBuf,
     pub state: DirectoryBufferState,
 }
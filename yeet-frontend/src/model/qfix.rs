@@ -1,14 +1,128 @@
-use std::path::PathBuf;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
-use yeet_buffer::model::SignIdentifier;
+use yeet_buffer::model::{viewport::ViewPort, Buffer, Cursor, SignIdentifier};
 
 pub const QFIX_SIGN_ID: SignIdentifier = "qfix";
+pub const QFIX_MAX_LISTS: usize = 10;
 
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct QuickFix {
-    pub current_index: usize,
     pub cdo: CdoState,
-    pub entries: Vec<PathBuf>,
+    pub current_list: usize,
+    pub lists: Vec<QuickFixList>,
+}
+
+impl Default for QuickFix {
+    fn default() -> Self {
+        Self {
+            cdo: CdoState::default(),
+            current_list: 0,
+            lists: vec![QuickFixList::new("manual")],
+        }
+    }
+}
+
+impl QuickFix {
+    // NOTE: lists always contain at least one entry, thus indexing is safe
+    pub fn current(&self) -> &QuickFixList {
+        &self.lists[self.current_list]
+    }
+
+    pub fn current_mut(&mut self) -> &mut QuickFixList {
+        &mut self.lists[self.current_list]
+    }
+
+    pub fn push(&mut self, list: QuickFixList) {
+        self.lists.truncate(self.current_list + 1);
+        self.lists.push(list);
+
+        if self.lists.len() > QFIX_MAX_LISTS {
+            self.lists.remove(0);
+        }
+
+        self.current_list = self.lists.len() - 1;
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct QuickFixList {
+    pub current_index: usize,
+    pub entries: Vec<QuickFixEntry>,
+    pub title: String,
+}
+
+impl QuickFixList {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.entries.iter().any(|entry| entry.path == path)
+    }
+
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = Vec::new();
+        for entry in self.entries.iter() {
+            if !paths.contains(&entry.path) {
+                paths.push(entry.path.clone());
+            }
+        }
+        paths
+    }
+
+    pub fn remove(&mut self, path: &Path) {
+        self.entries.retain(|entry| entry.path != path);
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuickFixEntry {
+    pub path: PathBuf,
+    pub location: Option<QuickFixLocation>,
+}
+
+impl Display for QuickFixEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = self.path.to_string_lossy();
+        match &self.location {
+            Some(location) => write!(
+                f,
+                "{}:{}:{} {}",
+                path, location.line, location.column, location.text
+            ),
+            None => write!(f, "{}", path),
+        }
+    }
+}
+
+impl From<PathBuf> for QuickFixEntry {
+    fn from(path: PathBuf) -> Self {
+        Self {
+            path,
+            location: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuickFixLocation {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+}
+
+#[derive(Default)]
+pub struct QuickFixEditor {
+    pub buffer: Buffer,
+    pub cursor: Option<Cursor>,
+    pub entries: Vec<QuickFixEntry>,
+    pub viewport: ViewPort,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
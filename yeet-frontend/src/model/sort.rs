@@ -0,0 +1,73 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::Deserialize;
+
+use crate::error::AppError;
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(try_from = "String")]
+pub struct SortMode {
+    pub kind: SortKind,
+    pub reverse: bool,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum SortKind {
+    DirectoriesFirst,
+    Extension,
+    Modified,
+    #[default]
+    Name,
+    Natural,
+    Size,
+}
+
+impl FromStr for SortMode {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (value, reverse) = match value.strip_suffix('!') {
+            Some(value) => (value, true),
+            None => (value, false),
+        };
+
+        let kind = match value {
+            "dirfirst" => SortKind::DirectoriesFirst,
+            "extension" => SortKind::Extension,
+            "mtime" => SortKind::Modified,
+            "name" => SortKind::Name,
+            "natural" => SortKind::Natural,
+            "size" => SortKind::Size,
+            _ => return Err(AppError::InvalidSortMode(value.to_owned())),
+        };
+
+        Ok(Self { kind, reverse })
+    }
+}
+
+impl TryFrom<String> for SortMode {
+    type Error = AppError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for SortMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            SortKind::DirectoriesFirst => "dirfirst",
+            SortKind::Extension => "extension",
+            SortKind::Modified => "mtime",
+            SortKind::Name => "name",
+            SortKind::Natural => "natural",
+            SortKind::Size => "size",
+        };
+
+        if self.reverse {
+            write!(f, "{}!", kind)
+        } else {
+            write!(f, "{}", kind)
+        }
+    }
+}
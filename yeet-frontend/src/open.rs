@@ -9,8 +9,10 @@ use std::{
 
 use tokio::process::Command;
 
+use crate::remote;
+
 #[cfg(all(unix, not(target_os = "macos")))]
-pub async fn path(path: &Path) -> Result<ExitStatus, io::Error> {
+pub async fn path(path: &Path, remote: Option<&Path>) -> Result<ExitStatus, io::Error> {
     use std::{env, path::PathBuf};
 
     use tokio::fs;
@@ -60,16 +62,20 @@ pub async fn path(path: &Path) -> Result<ExitStatus, io::Error> {
     }
 
     let mut cmd = if is_wsl().await {
-        Command::new("wslview").arg(wsl_path(path)).spawn()?
+        remote::with_socket_env(&mut Command::new("wslview"), remote)
+            .arg(wsl_path(path))
+            .spawn()?
     } else {
-        Command::new("xdg-open").arg(path).spawn()?
+        remote::with_socket_env(&mut Command::new("xdg-open"), remote)
+            .arg(path)
+            .spawn()?
     };
     cmd.wait().await
 }
 
 #[cfg(target_os = "macos")]
-pub async fn path(path: &Path) -> Result<ExitStatus, io::Error> {
-    Command::new("/usr/bin/open")
+pub async fn path(path: &Path, remote: Option<&Path>) -> Result<ExitStatus, io::Error> {
+    remote::with_socket_env(&mut Command::new("/usr/bin/open"), remote)
         .arg(path)
         .spawn()?
         .wait()
@@ -77,8 +83,8 @@ pub async fn path(path: &Path) -> Result<ExitStatus, io::Error> {
 }
 
 #[cfg(target_os = "redox")]
-pub async fn path(path: &Path) -> Result<ExitStatus, io::Error> {
-    Command::new("/ui/bin/launcher")
+pub async fn path(path: &Path, remote: Option<&Path>) -> Result<ExitStatus, io::Error> {
+    remote::with_socket_env(&mut Command::new("/ui/bin/launcher"), remote)
         .arg(path)
         .spawn()?
         .wait()
@@ -86,7 +92,7 @@ pub async fn path(path: &Path) -> Result<ExitStatus, io::Error> {
 }
 
 #[cfg(windows)]
-pub async fn path(path: &Path) -> Result<ExitStatus, io::Error> {
+pub async fn path(path: &Path, remote: Option<&Path>) -> Result<ExitStatus, io::Error> {
     const CREATE_NO_WINDOW: u32 = 0x08000000;
 
     fn wrap_in_quotes<T: AsRef<OsStr>>(path: T) -> String {
@@ -97,7 +103,7 @@ pub async fn path(path: &Path) -> Result<ExitStatus, io::Error> {
         result.to_string_lossy().to_string()
     }
 
-    Command::new("cmd")
+    remote::with_socket_env(&mut Command::new("cmd"), remote)
         .args(&["/c", "start", "\"\"", &wrap_in_quotes(path)])
         .creation_flags(CREATE_NO_WINDOW)
         .spawn()?
@@ -0,0 +1,436 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::{
+    process::Command,
+    sync::{mpsc::Sender, oneshot},
+};
+use tokio_util::sync::CancellationToken;
+use yeet_keymap::message::{KeySequence, KeymapMessage};
+
+use crate::{
+    error::AppError,
+    event::{Envelope, Message, MessageSource},
+};
+
+pub const SOCKET_ENV: &str = "YEET_REMOTE_SOCKET";
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RemoteRequest {
+    AddToQuickFix { paths: Vec<PathBuf> },
+    ExecuteCommand { command: String },
+    NavigateToPath { path: PathBuf },
+    QueryState,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct RemoteResponse {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<RemoteState>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RemoteState {
+    pub current: PathBuf,
+    pub mode: String,
+    pub qfix: Vec<PathBuf>,
+    pub selected: Option<PathBuf>,
+}
+
+pub fn get_socket_path() -> Result<PathBuf, AppError> {
+    let directory = get_socket_directory()?;
+    Ok(directory.join(format!("yeet-{}.sock", std::process::id())))
+}
+
+// NOTE: the runtime dir is private to the user, the temp dir fallback gets a private sub directory
+#[cfg(unix)]
+fn get_socket_directory() -> Result<PathBuf, AppError> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+
+    if let Some(directory) = dirs::runtime_dir() {
+        return Ok(directory);
+    }
+
+    let directory = env::temp_dir().join(format!("yeet-{}", uzers::get_current_uid()));
+    if let Err(error) = fs::DirBuilder::new().mode(0o700).create(&directory) {
+        if error.kind() != std::io::ErrorKind::AlreadyExists {
+            return Err(error.into());
+        }
+    }
+
+    let metadata = fs::symlink_metadata(&directory)?;
+    if !metadata.is_dir() || metadata.uid() != uzers::get_current_uid() {
+        return Err(AppError::ExecutionFailed(format!(
+            "socket directory {:?} is not owned by the current user",
+            directory
+        )));
+    }
+
+    if metadata.permissions().mode() & 0o077 != 0 {
+        fs::set_permissions(&directory, fs::Permissions::from_mode(0o700))?;
+    }
+
+    Ok(directory)
+}
+
+#[cfg(not(unix))]
+fn get_socket_directory() -> Result<PathBuf, AppError> {
+    Ok(env::temp_dir())
+}
+
+// NOTE: programs started by yeet inherit the socket path to drive this instance
+pub fn with_socket_env<'a>(command: &'a mut Command, path: Option<&Path>) -> &'a mut Command {
+    if let Some(path) = path {
+        command.env(SOCKET_ENV, path);
+    }
+    command
+}
+
+// NOTE: sockets of other users and stale sockets of crashed instances are skipped, thus the
+// most recently started instance accepting connections is used
+#[cfg(unix)]
+async fn connect(directory: &Path) -> Result<tokio::net::UnixStream, AppError> {
+    use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+    use tokio::net::UnixStream;
+
+    let uid = uzers::get_current_uid();
+    let mut candidates: Vec<_> = fs::read_dir(directory)?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.starts_with("yeet-") && name.ends_with(".sock")
+        })
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if !metadata.file_type().is_socket() || metadata.uid() != uid {
+                return None;
+            }
+            Some((metadata.modified().ok()?, entry.path()))
+        })
+        .collect();
+
+    candidates.sort_by(|(left, _), (right, _)| right.cmp(left));
+
+    for (_, path) in candidates {
+        match UnixStream::connect(&path).await {
+            Ok(stream) => return Ok(stream),
+            Err(error) => tracing::debug!("connecting to {:?} failed: {:?}", path, error),
+        }
+    }
+
+    Err(AppError::ExecutionFailed(
+        "no running yeet instance found".to_owned(),
+    ))
+}
+
+pub fn to_request(content: &str) -> Result<RemoteRequest, AppError> {
+    let content = content.trim();
+    if content.starts_with('{') {
+        serde_json::from_str(content).map_err(|err| AppError::ExecutionFailed(err.to_string()))
+    } else {
+        Ok(RemoteRequest::ExecuteCommand {
+            command: content.to_owned(),
+        })
+    }
+}
+
+#[cfg(unix)]
+pub fn start(
+    path: &Path,
+    sender: Sender<Envelope>,
+    cancellation: CancellationToken,
+) -> Result<(), AppError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::UnixListener,
+    };
+
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+
+    let listener = UnixListener::bind(path)?;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+
+    tokio::spawn(async move {
+        loop {
+            let stream = tokio::select! {
+                _ = cancellation.cancelled() => break,
+                result = listener.accept() => match result {
+                    Ok((stream, _)) => stream,
+                    Err(error) => {
+                        tracing::error!("accepting remote connection failed: {:?}", error);
+                        continue;
+                    }
+                },
+            };
+
+            let sender = sender.clone();
+            tokio::spawn(async move {
+                let (reader, mut writer) = stream.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+
+                    let response = handle(&sender, &line).await;
+                    let mut content = match serde_json::to_string(&response) {
+                        Ok(it) => it,
+                        Err(error) => {
+                            tracing::error!("serializing remote response failed: {:?}", error);
+                            break;
+                        }
+                    };
+                    content.push('\n');
+
+                    if writer.write_all(content.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn start(
+    _path: &Path,
+    _sender: Sender<Envelope>,
+    _cancellation: CancellationToken,
+) -> Result<(), AppError> {
+    Ok(())
+}
+
+pub fn stop(path: &Path) {
+    if let Err(error) = fs::remove_file(path) {
+        tracing::warn!("removing remote socket failed: {:?}", error);
+    }
+}
+
+async fn handle(sender: &Sender<Envelope>, line: &str) -> RemoteResponse {
+    let request = match serde_json::from_str::<RemoteRequest>(line) {
+        Ok(it) => it,
+        Err(error) => return to_error(error.to_string()),
+    };
+
+    tracing::debug!("received remote request: {:?}", request);
+
+    let (message, receiver) = match request {
+        RemoteRequest::AddToQuickFix { paths } => (Message::AddToQuickFix(paths), None),
+        RemoteRequest::ExecuteCommand { command } => (
+            Message::Keymap(KeymapMessage::ExecuteCommandString(command)),
+            None,
+        ),
+        RemoteRequest::NavigateToPath { path } => {
+            (Message::Keymap(KeymapMessage::NavigateToPath(path)), None)
+        }
+        RemoteRequest::QueryState => {
+            let (state_sender, state_receiver) = oneshot::channel();
+            (Message::StateRequested(state_sender), Some(state_receiver))
+        }
+    };
+
+    let envelope = Envelope {
+        messages: vec![message],
+        sequence: KeySequence::None,
+        source: MessageSource::Remote,
+    };
+
+    if let Err(error) = sender.send(envelope).await {
+        return to_error(error.to_string());
+    }
+
+    match receiver {
+        Some(receiver) => match receiver.await {
+            Ok(state) => RemoteResponse {
+                ok: true,
+                state: Some(state),
+                ..Default::default()
+            },
+            Err(_) => to_error("state request was dropped".to_owned()),
+        },
+        None => RemoteResponse {
+            ok: true,
+            ..Default::default()
+        },
+    }
+}
+
+fn to_error(error: String) -> RemoteResponse {
+    RemoteResponse {
+        ok: false,
+        error: Some(error),
+        ..Default::default()
+    }
+}
+
+#[cfg(unix)]
+pub async fn send(request: &RemoteRequest) -> Result<RemoteResponse, AppError> {
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::UnixStream,
+    };
+
+    let stream = match env::var(SOCKET_ENV) {
+        Ok(path) => UnixStream::connect(path).await?,
+        Err(_) => connect(&get_socket_directory()?).await?,
+    };
+    let (reader, mut writer) = stream.into_split();
+
+    let mut content =
+        serde_json::to_string(request).map_err(|err| AppError::ExecutionFailed(err.to_string()))?;
+    content.push('\n');
+    writer.write_all(content.as_bytes()).await?;
+
+    let mut lines = BufReader::new(reader).lines();
+    match lines.next_line().await? {
+        Some(line) => {
+            serde_json::from_str(&line).map_err(|err| AppError::ExecutionFailed(err.to_string()))
+        }
+        None => Err(AppError::ExecutionFailed(
+            "remote closed the connection".to_owned(),
+        )),
+    }
+}
+
+#[cfg(not(unix))]
+pub async fn send(_request: &RemoteRequest) -> Result<RemoteResponse, AppError> {
+    Err(AppError::ExecutionFailed(
+        "remote control is only supported on unix".to_owned(),
+    ))
+}
+
+mod test {
+    #[test]
+    fn to_request() {
+        use std::path::PathBuf;
+
+        use super::RemoteRequest;
+
+        let request = super::to_request(r#"{"type":"navigate_to_path","path":"/tmp"}"#);
+        assert_eq!(
+            RemoteRequest::NavigateToPath {
+                path: PathBuf::from("/tmp")
+            },
+            request.expect("valid request")
+        );
+
+        let request = super::to_request(r#"{"type":"query_state"}"#);
+        assert_eq!(RemoteRequest::QueryState, request.expect("valid request"));
+
+        let request = super::to_request("cl");
+        assert_eq!(
+            RemoteRequest::ExecuteCommand {
+                command: "cl".to_owned()
+            },
+            request.expect("valid request")
+        );
+
+        assert!(super::to_request(r#"{"type":"unknown"}"#).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn start_handles_requests() {
+        use std::{os::unix::fs::PermissionsExt, path::PathBuf};
+
+        use tokio::{
+            io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+            net::UnixStream,
+            sync::mpsc,
+        };
+        use tokio_util::sync::CancellationToken;
+
+        use crate::event::{Message, MessageSource};
+
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let path = directory.path().join("yeet-test.sock");
+        let (sender, mut receiver) = mpsc::channel(1);
+        let cancellation = CancellationToken::new();
+        super::start(&path, sender, cancellation.clone()).expect("start remote");
+
+        let mode = std::fs::metadata(&path)
+            .expect("metadata")
+            .permissions()
+            .mode();
+        assert_eq!(0o600, mode & 0o777);
+
+        let stream = UnixStream::connect(&path).await.expect("connect");
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        writer
+            .write_all(b"{\"type\":\"add_to_quick_fix\",\"paths\":[\"/a\"]}\n")
+            .await
+            .expect("write request");
+
+        let envelope = receiver.recv().await.expect("envelope");
+        assert_eq!(MessageSource::Remote, envelope.source);
+        assert!(matches!(
+            envelope.messages.as_slice(),
+            [Message::AddToQuickFix(paths)] if paths == &vec![PathBuf::from("/a")]
+        ));
+
+        let response = lines.next_line().await.expect("read").expect("line");
+        assert_eq!(r#"{"ok":true}"#, response);
+
+        writer
+            .write_all(b"{\"type\":\"query_state\"}\n")
+            .await
+            .expect("write request");
+
+        let envelope = receiver.recv().await.expect("envelope");
+        let state_sender = match envelope.messages.into_iter().next() {
+            Some(Message::StateRequested(sender)) => sender,
+            _ => panic!("expected state request"),
+        };
+        state_sender
+            .send(super::RemoteState {
+                current: PathBuf::from("/home"),
+                mode: "navigation".to_owned(),
+                ..Default::default()
+            })
+            .expect("send state");
+
+        let response = lines.next_line().await.expect("read").expect("line");
+        assert!(response.contains(r#""current":"/home""#));
+
+        cancellation.cancel();
+        super::stop(&path);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn connect_skips_stale_sockets() {
+        use std::{fs, os::unix::net::UnixListener};
+
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let directory = temp.path();
+
+        assert!(super::connect(directory).await.is_err());
+
+        let _listener = UnixListener::bind(directory.join("yeet-1.sock")).expect("bind");
+        drop(UnixListener::bind(directory.join("yeet-2.sock")).expect("bind"));
+        fs::write(directory.join("yeet-3.sock"), b"").expect("write file");
+
+        let stream = super::connect(directory).await.expect("connect");
+        let peer = stream.peer_addr().expect("peer address");
+        assert_eq!(
+            Some(directory.join("yeet-1.sock").as_path()),
+            peer.as_pathname()
+        );
+    }
+}
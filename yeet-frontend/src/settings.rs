@@ -1,32 +1,424 @@
-use std::path::PathBuf;
+use std::{fs, path::PathBuf};
 
+use serde::Deserialize;
 use yeet_buffer::model::viewport::WindowSettings;
 
+use crate::{
+    error::AppError,
+    model::{conflict::ConflictPolicy, junkyard::TrashBackend, sort::SortMode},
+};
+
 #[derive(Debug)]
 pub struct Settings {
+    pub conflict_policy: ConflictPolicy,
     pub current: WindowSettings,
+    pub ignore_patterns: Vec<String>,
+    pub junkyard: JunkYardSettings,
+    pub open_text_in_editor: bool,
+    pub openers: Vec<OpenerRule>,
     pub parent: WindowSettings,
     pub preview: WindowSettings,
     pub selection_to_file_on_open: Option<PathBuf>,
     pub selection_to_stdout_on_open: bool,
+    pub show_border: bool,
+    pub show_hidden: bool,
     pub show_quickfix_signs: bool,
     pub show_mark_signs: bool,
+    pub show_mtime: bool,
+    pub show_owner: bool,
+    pub show_permissions: bool,
+    pub show_size: bool,
+    pub sort: SortMode,
     pub startup_path: Option<PathBuf>,
+    pub trash_backend: TrashBackend,
+    pub use_gitignore: bool,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            conflict_policy: ConflictPolicy::default(),
             current: WindowSettings {
                 sign_column_width: 2,
             },
-            parent: WindowSettings::default(),
-            preview: WindowSettings::default(),
+            ignore_patterns: Vec::new(),
+            junkyard: JunkYardSettings::default(),
+            open_text_in_editor: false,
+            openers: Vec::new(),
+            parent: WindowSettings {
+                sign_column_width: 2,
+            },
+            preview: WindowSettings {
+                sign_column_width: 2,
+            },
             selection_to_file_on_open: None,
             selection_to_stdout_on_open: false,
+            show_border: true,
+            show_hidden: false,
             show_mark_signs: true,
+            show_mtime: false,
+            show_owner: false,
+            show_permissions: false,
             show_quickfix_signs: true,
+            show_size: false,
+            sort: SortMode::default(),
             startup_path: None,
+            trash_backend: TrashBackend::default(),
+            use_gitignore: true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct JunkYardSettings {
+    pub compress: bool,
+    pub max_age_days: u64,
+    pub max_count: usize,
+    pub max_size_mb: u64,
+}
+
+impl Default for JunkYardSettings {
+    fn default() -> Self {
+        Self {
+            compress: false,
+            max_age_days: 0,
+            max_count: 9,
+            max_size_mb: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct OpenerRule {
+    pub block: bool,
+    pub command: String,
+    pub detach: bool,
+    pub extensions: Vec<String>,
+    pub foreground: bool,
+    pub mime: Option<String>,
+}
+
+impl Settings {
+    pub fn from_config_file() -> Result<Self, AppError> {
+        let mut settings = Settings::default();
+
+        let config_path = get_config_path()?;
+        if !config_path.exists() {
+            tracing::debug!("config file does not exist on path {:?}", config_path);
+
+            return Ok(settings);
+        }
+
+        let content = fs::read_to_string(config_path)?;
+        let config: SettingsConfig = match toml::from_str(&content) {
+            Ok(it) => it,
+            Err(err) => return Err(AppError::LoadSettingsFailed(err.message().to_owned())),
+        };
+
+        config.apply(&mut settings);
+
+        Ok(settings)
+    }
+
+    pub fn set(&mut self, option: &str, value: &str) -> Result<(), AppError> {
+        let invalid_value = || AppError::InvalidSettingsOption(format!("{}={}", option, value));
+        match option {
+            "conflict_policy" => self.conflict_policy = value.parse()?,
+            "current.sign_column_width" => {
+                self.current.sign_column_width = value.parse().map_err(|_| invalid_value())?
+            }
+            "parent.sign_column_width" => {
+                self.parent.sign_column_width = value.parse().map_err(|_| invalid_value())?
+            }
+            "preview.sign_column_width" => {
+                self.preview.sign_column_width = value.parse().map_err(|_| invalid_value())?
+            }
+            "junkyard.compress" => {
+                self.junkyard.compress = value.parse().map_err(|_| invalid_value())?
+            }
+            "junkyard.max_age_days" => {
+                self.junkyard.max_age_days = value.parse().map_err(|_| invalid_value())?
+            }
+            "junkyard.max_count" => {
+                self.junkyard.max_count = value.parse().map_err(|_| invalid_value())?
+            }
+            "junkyard.max_size_mb" => {
+                self.junkyard.max_size_mb = value.parse().map_err(|_| invalid_value())?
+            }
+            "ignore_patterns" => {
+                self.ignore_patterns = value
+                    .split(',')
+                    .map(|pattern| pattern.trim())
+                    .filter(|pattern| !pattern.is_empty())
+                    .map(|pattern| pattern.to_owned())
+                    .collect()
+            }
+            "open_text_in_editor" => {
+                self.open_text_in_editor = value.parse().map_err(|_| invalid_value())?
+            }
+            "show_border" => self.show_border = value.parse().map_err(|_| invalid_value())?,
+            "show_hidden" => self.show_hidden = value.parse().map_err(|_| invalid_value())?,
+            "show_mark_signs" => {
+                self.show_mark_signs = value.parse().map_err(|_| invalid_value())?
+            }
+            "show_mtime" => self.show_mtime = value.parse().map_err(|_| invalid_value())?,
+            "show_owner" => self.show_owner = value.parse().map_err(|_| invalid_value())?,
+            "show_permissions" => {
+                self.show_permissions = value.parse().map_err(|_| invalid_value())?
+            }
+            "show_quickfix_signs" => {
+                self.show_quickfix_signs = value.parse().map_err(|_| invalid_value())?
+            }
+            "show_size" => self.show_size = value.parse().map_err(|_| invalid_value())?,
+            "sort" => self.sort = value.parse()?,
+            "trash_backend" => self.trash_backend = value.parse()?,
+            "use_gitignore" => self.use_gitignore = value.parse().map_err(|_| invalid_value())?,
+            _ => return Err(AppError::InvalidSettingsOption(option.to_owned())),
+        };
+
+        Ok(())
+    }
+
+    pub fn toggle(&mut self, option: &str) -> Result<(), AppError> {
+        let value = match option {
+            "open_text_in_editor" => &mut self.open_text_in_editor,
+            "show_border" => &mut self.show_border,
+            "show_hidden" => &mut self.show_hidden,
+            "show_mark_signs" => &mut self.show_mark_signs,
+            "show_mtime" => &mut self.show_mtime,
+            "show_owner" => &mut self.show_owner,
+            "show_permissions" => &mut self.show_permissions,
+            "show_quickfix_signs" => &mut self.show_quickfix_signs,
+            "show_size" => &mut self.show_size,
+            "use_gitignore" => &mut self.use_gitignore,
+            _ => return Err(AppError::InvalidSettingsOption(option.to_owned())),
+        };
+
+        *value = !*value;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SettingsConfig {
+    conflict_policy: Option<ConflictPolicy>,
+    current: WindowSettingsConfig,
+    ignore_patterns: Option<Vec<String>>,
+    junkyard: JunkYardSettingsConfig,
+    open_text_in_editor: Option<bool>,
+    opener: Option<Vec<OpenerRule>>,
+    parent: WindowSettingsConfig,
+    preview: WindowSettingsConfig,
+    show_border: Option<bool>,
+    show_hidden: Option<bool>,
+    show_mark_signs: Option<bool>,
+    show_mtime: Option<bool>,
+    show_owner: Option<bool>,
+    show_permissions: Option<bool>,
+    show_quickfix_signs: Option<bool>,
+    show_size: Option<bool>,
+    sort: Option<SortMode>,
+    trash_backend: Option<TrashBackend>,
+    use_gitignore: Option<bool>,
+}
+
+impl SettingsConfig {
+    fn apply(self, settings: &mut Settings) {
+        self.current.apply(&mut settings.current);
+        self.junkyard.apply(&mut settings.junkyard);
+        self.parent.apply(&mut settings.parent);
+        self.preview.apply(&mut settings.preview);
+
+        if let Some(conflict_policy) = self.conflict_policy {
+            settings.conflict_policy = conflict_policy;
+        }
+
+        if let Some(ignore_patterns) = self.ignore_patterns {
+            settings.ignore_patterns = ignore_patterns;
+        }
+
+        if let Some(open_text_in_editor) = self.open_text_in_editor {
+            settings.open_text_in_editor = open_text_in_editor;
+        }
+
+        if let Some(opener) = self.opener {
+            settings.openers = opener;
+        }
+
+        if let Some(show_border) = self.show_border {
+            settings.show_border = show_border;
+        }
+
+        if let Some(show_hidden) = self.show_hidden {
+            settings.show_hidden = show_hidden;
+        }
+
+        if let Some(show_mark_signs) = self.show_mark_signs {
+            settings.show_mark_signs = show_mark_signs;
+        }
+
+        if let Some(show_mtime) = self.show_mtime {
+            settings.show_mtime = show_mtime;
+        }
+
+        if let Some(show_owner) = self.show_owner {
+            settings.show_owner = show_owner;
+        }
+
+        if let Some(show_permissions) = self.show_permissions {
+            settings.show_permissions = show_permissions;
+        }
+
+        if let Some(show_quickfix_signs) = self.show_quickfix_signs {
+            settings.show_quickfix_signs = show_quickfix_signs;
+        }
+
+        if let Some(show_size) = self.show_size {
+            settings.show_size = show_size;
+        }
+
+        if let Some(sort) = self.sort {
+            settings.sort = sort;
+        }
+
+        if let Some(trash_backend) = self.trash_backend {
+            settings.trash_backend = trash_backend;
+        }
+
+        if let Some(use_gitignore) = self.use_gitignore {
+            settings.use_gitignore = use_gitignore;
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct WindowSettingsConfig {
+    sign_column_width: Option<usize>,
+}
+
+impl WindowSettingsConfig {
+    fn apply(self, settings: &mut WindowSettings) {
+        if let Some(sign_column_width) = self.sign_column_width {
+            settings.sign_column_width = sign_column_width;
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct JunkYardSettingsConfig {
+    compress: Option<bool>,
+    max_age_days: Option<u64>,
+    max_count: Option<usize>,
+    max_size_mb: Option<u64>,
+}
+
+impl JunkYardSettingsConfig {
+    fn apply(self, settings: &mut JunkYardSettings) {
+        if let Some(compress) = self.compress {
+            settings.compress = compress;
+        }
+
+        if let Some(max_age_days) = self.max_age_days {
+            settings.max_age_days = max_age_days;
         }
+
+        if let Some(max_count) = self.max_count {
+            settings.max_count = max_count;
+        }
+
+        if let Some(max_size_mb) = self.max_size_mb {
+            settings.max_size_mb = max_size_mb;
+        }
+    }
+}
+
+fn get_config_path() -> Result<PathBuf, AppError> {
+    match dirs::config_dir() {
+        Some(config_dir) => Ok(config_dir.join("yeet").join("config.toml")),
+        None => Err(AppError::LoadSettingsFailed(
+            "config directory could not be resolved".to_owned(),
+        )),
+    }
+}
+
+mod test {
+    #[test]
+    fn apply_config() {
+        let mut settings = super::Settings::default();
+        let config: super::SettingsConfig = toml::from_str(
+            r#"
+            show_border = false
+            sort = "dirfirst"
+
+            [junkyard]
+            compress = true
+            max_count = 50
+
+            [preview]
+            sign_column_width = 0
+
+            [[opener]]
+            mime = "video/*"
+            command = "mpv"
+            detach = true
+            "#,
+        )
+        .expect("valid config");
+
+        config.apply(&mut settings);
+
+        assert!(!settings.show_border);
+        assert!(settings.show_mark_signs);
+        assert_eq!(settings.current.sign_column_width, 2);
+        assert_eq!(settings.preview.sign_column_width, 0);
+        assert_eq!(settings.sort.to_string(), "dirfirst");
+        assert!(settings.junkyard.compress);
+        assert_eq!(settings.junkyard.max_count, 50);
+        assert_eq!(settings.junkyard.max_size_mb, 0);
+        assert_eq!(settings.openers.len(), 1);
+        assert_eq!(settings.openers[0].mime.as_deref(), Some("video/*"));
+        assert!(settings.openers[0].detach);
+        assert!(!settings.openers[0].foreground);
+    }
+
+    #[test]
+    fn set() {
+        let mut settings = super::Settings::default();
+
+        assert!(settings.set("show_quickfix_signs", "false").is_ok());
+        assert!(!settings.show_quickfix_signs);
+
+        assert!(settings.set("parent.sign_column_width", "4").is_ok());
+        assert_eq!(settings.parent.sign_column_width, 4);
+
+        assert!(settings.set("ignore_patterns", "target/, *.log").is_ok());
+        assert_eq!(settings.ignore_patterns, vec!["target/", "*.log"]);
+
+        assert!(settings.set("sort", "mtime!").is_ok());
+        assert_eq!(settings.sort.to_string(), "mtime!");
+
+        assert!(settings.set("show_border", "maybe").is_err());
+        assert!(settings.set("sort", "random").is_err());
+        assert!(settings.set("conflict_policy", "merge").is_err());
+        assert!(settings.set("trash_backend", "recycle").is_err());
+        assert!(settings.set("unknown", "true").is_err());
+    }
+
+    #[test]
+    fn toggle() {
+        let mut settings = super::Settings::default();
+
+        assert!(settings.toggle("show_hidden").is_ok());
+        assert!(settings.show_hidden);
+
+        assert!(settings.toggle("show_hidden").is_ok());
+        assert!(!settings.show_hidden);
+
+        assert!(settings.toggle("current.sign_column_width").is_err());
     }
 }
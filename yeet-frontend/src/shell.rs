@@ -0,0 +1,159 @@
+use std::{
+    io,
+    path::Path,
+    process::{ExitStatus, Stdio},
+};
+
+use tokio::process::Command;
+
+use crate::remote;
+
+#[cfg(not(windows))]
+pub fn command(base: &Path, command: &str, remote: Option<&Path>) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command).current_dir(base);
+    remote::with_socket_env(&mut shell, remote);
+    shell
+}
+
+#[cfg(windows)]
+pub fn command(base: &Path, command: &str, remote: Option<&Path>) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command).current_dir(base);
+    remote::with_socket_env(&mut shell, remote);
+    shell
+}
+
+#[cfg(not(windows))]
+pub fn quote(argument: &str) -> String {
+    format!("'{}'", argument.replace('\'', r"'\''"))
+}
+
+#[cfg(windows)]
+pub fn quote(argument: &str) -> String {
+    format!("\"{}\"", argument.replace('"', "\"\""))
+}
+
+// NOTE: %s is replaced with the quoted path, otherwise the path is appended
+pub fn with_path(command: &str, path: &Path) -> String {
+    let path = quote(&path.to_string_lossy());
+
+    let mut is_path_expanded = false;
+    let expanded = expand(command, |placeholder| match placeholder {
+        's' => {
+            is_path_expanded = true;
+            Some(path.clone())
+        }
+        _ => None,
+    });
+
+    if is_path_expanded {
+        expanded
+    } else {
+        format!("{} {}", expanded, path)
+    }
+}
+
+// NOTE: %% is a literal percent sign, unresolved placeholders are kept as they are
+pub fn expand(command: &str, mut resolve: impl FnMut(char) -> Option<String>) -> String {
+    let mut expanded = String::new();
+    let mut chars = command.chars();
+    while let Some(char) = chars.next() {
+        if char != '%' {
+            expanded.push(char);
+            continue;
+        }
+
+        match chars.next() {
+            Some('%') => expanded.push('%'),
+            Some(next) => match resolve(next) {
+                Some(value) => expanded.push_str(&value),
+                None => {
+                    expanded.push('%');
+                    expanded.push(next);
+                }
+            },
+            None => expanded.push('%'),
+        }
+    }
+    expanded
+}
+
+// NOTE: a new process group prevents signals like SIGHUP from reaching detached programs
+#[cfg(unix)]
+pub fn detach(command: &mut Command) {
+    command.process_group(0);
+}
+
+#[cfg(not(unix))]
+pub fn detach(_command: &mut Command) {}
+
+pub async fn foreground(
+    base: &Path,
+    command: &str,
+    remote: Option<&Path>,
+) -> Result<ExitStatus, io::Error> {
+    self::command(base, command, remote).spawn()?.wait().await
+}
+
+pub async fn background(
+    base: &Path,
+    command: &str,
+    detach: bool,
+    block: bool,
+    remote: Option<&Path>,
+) -> Result<(), io::Error> {
+    let mut shell = self::command(base, command, remote);
+    shell
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    if detach {
+        self::detach(&mut shell);
+    }
+
+    let mut child = shell.spawn()?;
+    if block {
+        child.wait().await?;
+    }
+
+    Ok(())
+}
+
+mod test {
+    #[test]
+    fn with_path() {
+        use std::path::Path;
+
+        let path = Path::new("/tmp/file");
+        let quoted = super::quote("/tmp/file");
+
+        assert_eq!(format!("mpv {}", quoted), super::with_path("mpv", path));
+        assert_eq!(
+            format!("convert {} 50%", quoted),
+            super::with_path("convert %s 50%%", path)
+        );
+        assert_eq!(
+            format!("printf %s {}", quoted),
+            super::with_path("printf %%s", path)
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_with_remote() {
+        use std::path::Path;
+
+        let output = super::command(
+            Path::new("/"),
+            "printf %s \"$YEET_REMOTE_SOCKET\"",
+            Some(Path::new("/run/yeet.sock")),
+        )
+        .output()
+        .await
+        .expect("run shell");
+
+        assert_eq!(b"/run/yeet.sock", output.stdout.as_slice());
+    }
+}
@@ -6,7 +6,13 @@ use std::{
 
 use tokio::process::Command;
 
-use crate::error::AppError;
+use yeet_keymap::message::PrintContent;
+
+use crate::{
+    error::AppError,
+    model::qfix::{QuickFixEntry, QuickFixLocation},
+    shell,
+};
 
 pub async fn fd(base_path: &Path, params: String) -> Result<Vec<PathBuf>, AppError> {
     tracing::debug!("executing fd at {:?} with {:?} params", base_path, params);
@@ -58,6 +64,111 @@ pub async fn fd(base_path: &Path, params: String) -> Result<Vec<PathBuf>, AppErr
     }
 }
 
+pub async fn rg(base_path: &Path, pattern: &str) -> Result<Vec<QuickFixEntry>, AppError> {
+    tracing::debug!("executing rg at {:?} with {:?} pattern", base_path, pattern);
+
+    let result = Command::new("rg")
+        .args([
+            "--color",
+            "never",
+            "--vimgrep",
+            "--only-matching",
+            "--null",
+            "--",
+        ])
+        .arg(pattern)
+        .arg(base_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await;
+
+    // NOTE: rg exits with 1 if nothing matched
+    match result {
+        Ok(output) => {
+            if !output.status.success() && output.status.code() != Some(1) {
+                let message = format!("rg failed: {:?}", output);
+                tracing::error!(message);
+                Err(AppError::ExecutionFailed(message))
+            } else if output.stdout.is_empty() {
+                let message = "rg failed: result is empty".to_string();
+                tracing::error!(message);
+                Err(AppError::ExecutionFailed(message))
+            } else {
+                let result = str::from_utf8(&output.stdout)
+                    .map_or(vec![], |s| s.lines().filter_map(to_qfix_entry).collect());
+                Ok(result)
+            }
+        }
+        Err(err) => {
+            let message = format!("rg failed: {:?}", err);
+            tracing::error!(message);
+            Err(AppError::ExecutionFailed(message))
+        }
+    }
+}
+
+pub async fn shell(
+    base_path: &Path,
+    command: &str,
+    remote: Option<&Path>,
+) -> Result<Vec<PrintContent>, AppError> {
+    tracing::debug!("executing shell command at {:?}: {:?}", base_path, command);
+
+    let result = shell::command(base_path, command, remote)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .output()
+        .await;
+
+    match result {
+        Ok(output) => {
+            let mut content = vec![PrintContent::Default(format!(":!{}", command))];
+            content.extend(
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .map(|line| PrintContent::Default(line.to_owned())),
+            );
+            content.extend(
+                String::from_utf8_lossy(&output.stderr)
+                    .lines()
+                    .map(|line| PrintContent::Error(line.to_owned())),
+            );
+
+            if !output.status.success() {
+                content.push(PrintContent::Error(format!(
+                    "shell returned {}",
+                    output.status
+                )));
+            }
+            Ok(content)
+        }
+        Err(err) => {
+            let message = format!("shell command failed: {:?}", err);
+            tracing::error!(message);
+            Err(AppError::ExecutionFailed(message))
+        }
+    }
+}
+
+fn to_qfix_entry(line: &str) -> Option<QuickFixEntry> {
+    let (path, location) = line.split_once('\0')?;
+    let mut location = location.splitn(3, ':');
+
+    let line = location.next()?.parse().ok()?;
+    let column = location.next()?.parse().ok()?;
+    let text = location.next()?.to_owned();
+
+    Some(QuickFixEntry {
+        path: PathBuf::from(path),
+        location: Some(QuickFixLocation { line, column, text }),
+    })
+}
+
 pub async fn zoxide(params: String) -> Result<PathBuf, AppError> {
     tracing::debug!("executing zoxide with {:?} params", params);
 
@@ -112,3 +223,21 @@ pub async fn zoxide(params: String) -> Result<PathBuf, AppError> {
         }
     }
 }
+
+mod test {
+    #[test]
+    fn to_qfix_entry_with_location() {
+        use std::path::PathBuf;
+
+        let line = "/src/main.rs\x0012:5:fn main: yes";
+        let entry = super::to_qfix_entry(line).expect("valid entry");
+        let location = entry.location.expect("location");
+
+        assert_eq!(PathBuf::from("/src/main.rs"), entry.path);
+        assert_eq!(12, location.line);
+        assert_eq!(5, location.column);
+        assert_eq!("fn main: yes", location.text);
+
+        assert!(super::to_qfix_entry("/src/main.rs:12:5:fn main").is_none());
+    }
+}
@@ -0,0 +1,250 @@
+use std::path::{Path, PathBuf};
+
+use tokio::{fs, sync::mpsc::Sender};
+use yeet_keymap::message::{KeymapMessage, PrintContent};
+
+use crate::{
+    error::AppError,
+    event::{Envelope, Message},
+    model::conflict::ConflictPolicy,
+};
+
+use super::{exists, to_envelope, Task};
+
+pub async fn resolve(
+    sender: &Sender<Envelope>,
+    target: &Path,
+    policy: &ConflictPolicy,
+    task: impl FnOnce() -> Task,
+) -> Result<Option<PathBuf>, AppError> {
+    if !exists(target) {
+        return Ok(Some(target.to_path_buf()));
+    }
+
+    match policy {
+        ConflictPolicy::Fail => Err(AppError::InvalidTargetPath),
+        ConflictPolicy::Overwrite => {
+            let metadata = fs::symlink_metadata(target).await?;
+            if metadata.is_dir() {
+                fs::remove_dir_all(target).await?;
+            } else {
+                fs::remove_file(target).await?;
+            }
+
+            report(sender, format!("overwrote existing {}", target.display())).await;
+
+            Ok(Some(target.to_path_buf()))
+        }
+        ConflictPolicy::Prompt => {
+            let _ = sender
+                .send(to_envelope(vec![Message::ConflictDetected(task())]))
+                .await;
+
+            Ok(None)
+        }
+        ConflictPolicy::Rename => {
+            let renamed = get_unique_path(target);
+            let message = format!("{} exists, using {}", target.display(), renamed.display());
+            report(sender, message).await;
+
+            Ok(Some(renamed))
+        }
+        ConflictPolicy::Skip => {
+            report(sender, format!("skipped, {} exists", target.display())).await;
+
+            Ok(None)
+        }
+    }
+}
+
+async fn report(sender: &Sender<Envelope>, message: String) {
+    let content = vec![PrintContent::Information(message)];
+    let _ = sender
+        .send(to_envelope(vec![Message::Keymap(KeymapMessage::Print(
+            content,
+        ))]))
+        .await;
+}
+
+fn get_unique_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_string());
+
+    let mut count = 0;
+    loop {
+        count += 1;
+
+        let name = match &extension {
+            Some(extension) => format!("{}_{}.{}", stem, count, extension),
+            None => format!("{}_{}", stem, count),
+        };
+
+        let candidate = path.with_file_name(name);
+        if !exists(&candidate) {
+            return candidate;
+        }
+    }
+}
+
+mod test {
+    #[tokio::test]
+    async fn resolve_with_fail() {
+        use tokio::sync::mpsc;
+
+        use crate::{error::AppError, model::conflict::ConflictPolicy, task::Task};
+
+        let (sender, _receiver) = mpsc::channel(1);
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let target = directory.path().to_path_buf();
+        let task = || Task::RenamePath(target.join("a"), target.clone(), ConflictPolicy::Fail);
+
+        let result = super::resolve(&sender, &target, &ConflictPolicy::Fail, task).await;
+        assert!(matches!(result, Err(AppError::InvalidTargetPath)));
+
+        let missing = target.join("missing");
+        let result = super::resolve(&sender, &missing, &ConflictPolicy::Fail, task).await;
+        assert_eq!(Some(missing.clone()), result.expect("no conflict"));
+    }
+
+    #[tokio::test]
+    async fn resolve_with_overwrite() {
+        use std::fs;
+
+        use tokio::sync::mpsc;
+        use yeet_keymap::message::{KeymapMessage, PrintContent};
+
+        use crate::{event::Message, model::conflict::ConflictPolicy, task::Task};
+
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let source = directory.path().join("source");
+        let target = directory.path().join("target");
+        fs::create_dir_all(target.join("nested")).expect("create target");
+
+        let (sender, mut receiver) = mpsc::channel(1);
+        let task = || Task::CopyPath(source.clone(), target.clone(), ConflictPolicy::Overwrite);
+
+        let result = super::resolve(&sender, &target, &ConflictPolicy::Overwrite, task).await;
+        assert_eq!(Some(target.clone()), result.expect("resolved"));
+        assert!(!target.exists());
+
+        let envelope = receiver.recv().await.expect("report");
+        assert!(matches!(
+            envelope.messages.as_slice(),
+            [Message::Keymap(KeymapMessage::Print(content))]
+                if matches!(content.as_slice(), [PrintContent::Information(message)] if message.contains("overwrote existing"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn resolve_with_rename() {
+        use std::fs;
+
+        use tokio::sync::mpsc;
+        use yeet_keymap::message::{KeymapMessage, PrintContent};
+
+        use crate::{event::Message, model::conflict::ConflictPolicy, task::Task};
+
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let source = directory.path().join("source.txt");
+        let target = directory.path().join("target.txt");
+        fs::write(&target, "target").expect("write target");
+        fs::write(directory.path().join("target_1.txt"), "taken").expect("write taken");
+
+        let (sender, mut receiver) = mpsc::channel(1);
+        let task = || Task::CopyPath(source.clone(), target.clone(), ConflictPolicy::Rename);
+
+        let result = super::resolve(&sender, &target, &ConflictPolicy::Rename, task).await;
+        assert_eq!(
+            Some(directory.path().join("target_2.txt")),
+            result.expect("resolved")
+        );
+        assert_eq!("target", fs::read_to_string(&target).expect("read target"));
+
+        let envelope = receiver.recv().await.expect("report");
+        assert!(matches!(
+            envelope.messages.as_slice(),
+            [Message::Keymap(KeymapMessage::Print(content))]
+                if matches!(content.as_slice(), [PrintContent::Information(message)] if message.contains("target_2.txt"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn resolve_with_skip() {
+        use std::fs;
+
+        use tokio::sync::mpsc;
+        use yeet_keymap::message::{KeymapMessage, PrintContent};
+
+        use crate::{event::Message, model::conflict::ConflictPolicy, task::Task};
+
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let source = directory.path().join("source");
+        let target = directory.path().join("target");
+        fs::write(&target, "target").expect("write target");
+
+        let (sender, mut receiver) = mpsc::channel(1);
+        let task = || Task::CopyPath(source.clone(), target.clone(), ConflictPolicy::Skip);
+
+        let result = super::resolve(&sender, &target, &ConflictPolicy::Skip, task).await;
+        assert_eq!(None, result.expect("resolved"));
+        assert_eq!("target", fs::read_to_string(&target).expect("read target"));
+
+        let envelope = receiver.recv().await.expect("report");
+        assert!(matches!(
+            envelope.messages.as_slice(),
+            [Message::Keymap(KeymapMessage::Print(content))]
+                if matches!(content.as_slice(), [PrintContent::Information(message)] if message.contains("skipped"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn resolve_with_prompt() {
+        use std::fs;
+
+        use tokio::sync::mpsc;
+
+        use crate::{event::Message, model::conflict::ConflictPolicy, task::Task};
+
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let source = directory.path().join("source");
+        let target = directory.path().join("target");
+        fs::write(&target, "target").expect("write target");
+
+        let (sender, mut receiver) = mpsc::channel(1);
+        let task = || Task::CopyPath(source.clone(), target.clone(), ConflictPolicy::Prompt);
+
+        let result = super::resolve(&sender, &target, &ConflictPolicy::Prompt, task).await;
+        assert_eq!(None, result.expect("resolved"));
+        assert!(target.exists());
+
+        let envelope = receiver.recv().await.expect("conflict");
+        assert!(matches!(
+            envelope.messages.as_slice(),
+            [Message::ConflictDetected(Task::CopyPath(_, path, ConflictPolicy::Prompt))]
+                if path == &target
+        ));
+    }
+
+    #[test]
+    fn get_unique_path() {
+        use std::path::Path;
+
+        let path = Path::new("/yeet-conflict-test/file.tar.gz");
+        assert_eq!(
+            super::get_unique_path(path),
+            Path::new("/yeet-conflict-test/file.tar_1.gz")
+        );
+
+        let path = Path::new("/yeet-conflict-test/.bashrc");
+        assert_eq!(
+            super::get_unique_path(path),
+            Path::new("/yeet-conflict-test/.bashrc_1")
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use std::{fs::Metadata, path::Path};
+
+use filetime::FileTime;
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncWriteExt},
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::error::AppError;
+
+use super::progress::ProgressReporter;
+
+const BUFFER_SIZE: usize = 1024 * 1024;
+
+pub async fn copy_path(
+    source: &Path,
+    target: &Path,
+    reporter: &mut ProgressReporter,
+    cancellation: &CancellationToken,
+) -> Result<(), AppError> {
+    if target.starts_with(source) {
+        return Err(AppError::InvalidTargetPath);
+    }
+
+    let mut directories = Vec::new();
+    let mut pending = vec![(source.to_path_buf(), target.to_path_buf())];
+    while let Some((source, target)) = pending.pop() {
+        if cancellation.is_cancelled() {
+            break;
+        }
+
+        let metadata = fs::symlink_metadata(&source).await?;
+        let file_type = metadata.file_type();
+        if file_type.is_symlink() {
+            copy_symlink(&source, &target).await?;
+            filetime::set_symlink_file_times(
+                &target,
+                FileTime::from_last_access_time(&metadata),
+                FileTime::from_last_modification_time(&metadata),
+            )?;
+        } else if file_type.is_dir() {
+            fs::create_dir(&target).await?;
+
+            let mut read_dir = fs::read_dir(&source).await?;
+            while let Some(entry) = read_dir.next_entry().await? {
+                pending.push((entry.path(), target.join(entry.file_name())));
+            }
+
+            directories.push((target, metadata));
+        } else {
+            copy_file(&source, &target, reporter, cancellation).await?;
+            if cancellation.is_cancelled() {
+                break;
+            }
+
+            set_permissions_and_times(&target, &metadata).await?;
+        }
+
+        reporter.add(1, 0);
+    }
+
+    // NOTE: set in reverse to keep read only directories writable while copying content
+    for (target, metadata) in directories.iter().rev() {
+        set_permissions_and_times(target, metadata).await?;
+    }
+
+    Ok(())
+}
+
+async fn copy_file(
+    source: &Path,
+    target: &Path,
+    reporter: &mut ProgressReporter,
+    cancellation: &CancellationToken,
+) -> Result<(), AppError> {
+    let mut reader = fs::File::open(source).await?;
+    let mut writer = fs::File::create(target).await?;
+
+    let mut buffer = vec![0; BUFFER_SIZE];
+    loop {
+        if cancellation.is_cancelled() {
+            drop(writer);
+            fs::remove_file(target).await?;
+
+            return Ok(());
+        }
+
+        let read = reader.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+
+        writer.write_all(&buffer[..read]).await?;
+        reporter.add(0, read as u64);
+    }
+
+    writer.flush().await?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+async fn copy_symlink(source: &Path, target: &Path) -> Result<(), AppError> {
+    let link = fs::read_link(source).await?;
+    fs::symlink(link, target).await?;
+
+    Ok(())
+}
+
+#[cfg(windows)]
+async fn copy_symlink(source: &Path, target: &Path) -> Result<(), AppError> {
+    let link = fs::read_link(source).await?;
+    if source.is_dir() {
+        fs::symlink_dir(link, target).await?;
+    } else {
+        fs::symlink_file(link, target).await?;
+    }
+
+    Ok(())
+}
+
+async fn set_permissions_and_times(target: &Path, metadata: &Metadata) -> Result<(), AppError> {
+    fs::set_permissions(target, metadata.permissions()).await?;
+    filetime::set_file_times(
+        target,
+        FileTime::from_last_access_time(metadata),
+        FileTime::from_last_modification_time(metadata),
+    )?;
+
+    Ok(())
+}
+
+mod test {
+    #[tokio::test]
+    async fn copy_path_recursive() {
+        use std::fs;
+
+        use tokio::sync::mpsc;
+        use tokio_util::sync::CancellationToken;
+
+        use crate::task::progress::ProgressReporter;
+
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let base = directory.path();
+        let source = base.join("source");
+        let target = base.join("target");
+
+        fs::create_dir_all(source.join("nested")).expect("create source");
+        fs::write(source.join("file"), "content").expect("write file");
+        fs::write(source.join("nested").join("inner"), "inner").expect("write inner");
+
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("file", source.join("link")).expect("create symlink");
+
+        let (sender, _receiver) = mpsc::channel(1);
+        let mut reporter = ProgressReporter::new(sender, "copy".to_owned());
+        let result =
+            super::copy_path(&source, &target, &mut reporter, &CancellationToken::new()).await;
+
+        assert!(result.is_ok());
+        assert_eq!(reporter.progress.bytes, 12);
+        assert_eq!(
+            fs::read_to_string(target.join("nested").join("inner")).expect("read inner"),
+            "inner"
+        );
+
+        #[cfg(unix)]
+        assert_eq!(
+            fs::read_link(target.join("link")).expect("read link"),
+            std::path::PathBuf::from("file")
+        );
+
+        let source_mtime = fs::metadata(source.join("file"))
+            .and_then(|m| m.modified())
+            .expect("source mtime");
+        let target_mtime = fs::metadata(target.join("file"))
+            .and_then(|m| m.modified())
+            .expect("target mtime");
+        assert_eq!(source_mtime, target_mtime);
+    }
+}
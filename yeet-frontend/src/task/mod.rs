@@ -23,31 +23,50 @@ use yeet_keymap::{
 
 use crate::{
     error::AppError,
-    event::{ContentKind, Envelope, Message, MessageSource},
+    event::{ContentKind, DirectoryContent, Envelope, Message, MessageSource, Preview},
     init::{
-        junkyard::{self, cache_and_compress, compress, restore},
+        junkyard::{self, compress, compress_uncompressed, restore},
         mark::{load_marks_from_file, save_marks_to_file},
+        qfix, trash,
+    },
+    model::{
+        conflict::ConflictPolicy,
+        gitignore,
+        junkyard::{FileEntry, FileEntryFormat, TrashBackend},
+        mark::Marks,
     },
-    model::{junkyard::FileEntry, mark::Marks},
 };
 
+use self::progress::ProgressReporter;
+
 mod command;
+mod conflict;
+mod copy;
 mod image;
+pub mod progress;
 mod syntax;
 
 pub enum Task {
     AddPath(PathBuf),
-    CopyPath(PathBuf, PathBuf),
+    CompressJunkYardEntry(FileEntry),
+    CopyPath(PathBuf, PathBuf, ConflictPolicy),
     DeleteMarks(Vec<char>),
     DeletePath(PathBuf),
     DeleteJunkYardEntry(FileEntry),
     EmitMessages(Vec<Message>),
     EnumerateDirectory(PathBuf, Option<String>),
     ExecuteFd(PathBuf, String),
+    ExecuteRg(PathBuf, String),
+    ExecuteShell(PathBuf, String),
     ExecuteZoxide(String),
+    ExportQuickFix(PathBuf, String),
+    ImportQuickFix(PathBuf, PathBuf),
+    LoadArchivePreview(FileEntry),
     LoadPreview(PathBuf, Rect),
-    RenamePath(PathBuf, PathBuf),
-    RestorePath(FileEntry, PathBuf),
+    MeasureJunkYardEntry(PathBuf),
+    RenamePath(PathBuf, PathBuf, ConflictPolicy),
+    RestorePath(FileEntry, PathBuf, ConflictPolicy),
+    Sequence(Vec<Task>),
     TrashPath(FileEntry),
     YankPath(FileEntry),
 }
@@ -56,17 +75,35 @@ impl Task {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Task::AddPath(path) => write!(f, "AddPath({:?})", path),
-            Task::CopyPath(src, dst) => write!(f, "CopyPath({:?}, {:?})", src, dst),
+            Task::CompressJunkYardEntry(entry) => write!(f, "CompressJunkYardEntry({:?})", entry),
+            Task::CopyPath(src, dst, policy) => {
+                write!(f, "CopyPath({:?}, {:?}, {})", src, dst, policy)
+            }
             Task::DeleteMarks(marks) => write!(f, "DeleteMarks({:?})", marks),
             Task::DeletePath(path) => write!(f, "DeletePath({:?})", path),
             Task::DeleteJunkYardEntry(entry) => write!(f, "DeleteJunkYardEntry({:?})", entry),
             Task::EmitMessages(_) => write!(f, "EmitMessages"),
             Task::EnumerateDirectory(path, _) => write!(f, "EnumerateDirectory({:?}, _)", path),
             Task::ExecuteFd(base, params) => write!(f, "ExecuteFd({:?}, {:?})", base, params),
+            Task::ExecuteRg(base, pattern) => write!(f, "ExecuteRg({:?}, {:?})", base, pattern),
+            Task::ExecuteShell(base, command) => {
+                write!(f, "ExecuteShell({:?}, {:?})", base, command)
+            }
             Task::ExecuteZoxide(params) => write!(f, "ExecuteZoxide({:?})", params),
+            Task::ExportQuickFix(path, _) => write!(f, "ExportQuickFix({:?}, _)", path),
+            Task::ImportQuickFix(path, base) => {
+                write!(f, "ImportQuickFix({:?}, {:?})", path, base)
+            }
+            Task::LoadArchivePreview(entry) => write!(f, "LoadArchivePreview({:?})", entry),
             Task::LoadPreview(path, rect) => write!(f, "LoadPreview({:?}, {})", path, rect),
-            Task::RenamePath(old, new) => write!(f, "RenamePath({:?}, {:?})", old, new),
-            Task::RestorePath(entry, path) => write!(f, "RestorePath({:?}, {:?})", entry, path),
+            Task::MeasureJunkYardEntry(path) => write!(f, "MeasureJunkYardEntry({:?})", path),
+            Task::RenamePath(old, new, policy) => {
+                write!(f, "RenamePath({:?}, {:?}, {})", old, new, policy)
+            }
+            Task::RestorePath(entry, path, policy) => {
+                write!(f, "RestorePath({:?}, {:?}, {})", entry, path, policy)
+            }
+            Task::Sequence(tasks) => write!(f, "Sequence({:?})", tasks),
             Task::TrashPath(entry) => write!(f, "TrashPath({:?})", entry),
             Task::YankPath(entry) => write!(f, "YankPath({:?})", entry),
         }
@@ -91,16 +128,26 @@ impl PartialEq for Task {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Task::AddPath(p1), Task::AddPath(p2)) => p1 == p2,
-            (Task::CopyPath(s1, t1), Task::CopyPath(s2, t2)) => s1 == s2 && t1 == t2,
+            (Task::CompressJunkYardEntry(e1), Task::CompressJunkYardEntry(e2)) => e1 == e2,
+            (Task::CopyPath(s1, t1, p1), Task::CopyPath(s2, t2, p2)) => {
+                s1 == s2 && t1 == t2 && p1 == p2
+            }
             (Task::DeleteMarks(m1), Task::DeleteMarks(m2)) => m1 == m2,
             (Task::DeletePath(p1), Task::DeletePath(p2)) => p1 == p2,
             (Task::DeleteJunkYardEntry(e1), Task::DeleteJunkYardEntry(e2)) => e1 == e2,
             (Task::EnumerateDirectory(p1, s1), Task::EnumerateDirectory(p2, s2)) => {
                 p1 == p2 && s1 == s2
             }
+            (Task::LoadArchivePreview(e1), Task::LoadArchivePreview(e2)) => e1 == e2,
             (Task::LoadPreview(p1, r1), Task::LoadPreview(p2, r2)) => p1 == p2 && r1 == r2,
-            (Task::RenamePath(o1, n1), Task::RenamePath(o2, n2)) => o1 == o2 && n1 == n2,
-            (Task::RestorePath(e1, p1), Task::RestorePath(e2, p2)) => e1 == e2 && p1 == p2,
+            (Task::MeasureJunkYardEntry(p1), Task::MeasureJunkYardEntry(p2)) => p1 == p2,
+            (Task::RenamePath(o1, n1, p1), Task::RenamePath(o2, n2, p2)) => {
+                o1 == o2 && n1 == n2 && p1 == p2
+            }
+            (Task::RestorePath(e1, t1, p1), Task::RestorePath(e2, t2, p2)) => {
+                e1 == e2 && t1 == t2 && p1 == p2
+            }
+            (Task::Sequence(t1), Task::Sequence(t2)) => t1 == t2,
             (Task::TrashPath(e1), Task::TrashPath(e2)) => e1 == e2,
             (Task::YankPath(e1), Task::YankPath(e2)) => e1 == e2,
             _ => false,
@@ -118,6 +165,7 @@ impl TaskManager {
     pub fn new(
         sender: Sender<Envelope>,
         resolver: Arc<Mutex<MessageResolver>>,
+        remote: Option<PathBuf>,
         cancellation: CancellationToken,
     ) -> Self {
         let picker = resolve_picker();
@@ -148,6 +196,7 @@ impl TaskManager {
                         let resolver = resolver.clone();
                         let highlighter = highlighter.clone();
                         let picker = picker.clone();
+                        let remote = remote.clone();
 
                         tokio::spawn(async move {
                             let id = task.to_string();
@@ -158,6 +207,7 @@ impl TaskManager {
                                 resolver,
                                 highlighter,
                                 picker,
+                                remote.as_deref(),
                                 task,
                                 child_token
                             ).await
@@ -195,9 +245,11 @@ async fn run_task(
     resolver: Arc<Mutex<MessageResolver>>,
     highlighter: Arc<Mutex<(SyntaxSet, ThemeSet)>>,
     picker: Arc<Mutex<Option<Picker>>>,
+    remote: Option<&Path>,
     task: Task,
     cancellation: CancellationToken,
 ) -> Result<(), AppError> {
+    let identifier = task.to_string();
     match task {
         Task::AddPath(path) => {
             if path.exists() {
@@ -220,12 +272,38 @@ async fn run_task(
                 }
             }
         }
-        Task::CopyPath(source, target) => {
-            if !source.exists() || target.exists() {
+        Task::CompressJunkYardEntry(entry) => {
+            let mut reporter = ProgressReporter::new(sender.clone(), identifier);
+            if let Err(error) = compress_uncompressed(entry, &mut reporter).await {
+                emit_error(sender, error).await;
+            }
+        }
+        Task::CopyPath(source, target, policy) => {
+            if !exists(&source) || is_target_invalid(&source, &target, &policy) {
                 return Err(AppError::InvalidTargetPath);
             }
 
-            fs::copy(source, target).await?;
+            let task = || Task::CopyPath(source.clone(), target.clone(), policy.clone());
+            let target = match conflict::resolve(sender, &target, &policy, task).await? {
+                Some(it) => it,
+                None => return Ok(()),
+            };
+
+            let mut reporter = ProgressReporter::new(sender.clone(), identifier);
+            let result = copy::copy_path(&source, &target, &mut reporter, &cancellation).await;
+
+            if cancellation.is_cancelled() || result.is_err() {
+                let message = format!(
+                    "copy of {:?} interrupted after {} were copied to {:?}",
+                    source, reporter.progress, target
+                );
+
+                let _ = sender
+                    .send(to_envelope(vec![Message::Error(message)]))
+                    .await;
+            }
+
+            result?;
         }
         Task::DeleteMarks(marks) => {
             let mut current = Marks::default();
@@ -241,19 +319,14 @@ async fn run_task(
                 }
             }
         }
-        Task::DeletePath(path) => {
-            if !path.exists() {
-                return Err(AppError::InvalidTargetPath);
+        Task::DeletePath(path) => delete_path(&path).await?,
+        Task::DeleteJunkYardEntry(entry) => {
+            let result = match entry.backend {
+                TrashBackend::Freedesktop => trash::delete(&entry).await,
+                TrashBackend::JunkYard => junkyard::delete(entry).await,
             };
 
-            if path.is_file() {
-                fs::remove_file(&path).await?;
-            } else if path.is_dir() {
-                fs::remove_dir_all(&path).await?;
-            }
-        }
-        Task::DeleteJunkYardEntry(entry) => {
-            if let Err(error) = junkyard::delete(entry).await {
+            if let Err(error) = result {
                 tracing::error!("deleting junk yard entry failed: {:?}", error);
             }
         }
@@ -296,22 +369,33 @@ async fn run_task(
             }
 
             let read_dir = fs::read_dir(path.clone()).await;
+            let gitignore = gitignore::load(&path);
+            let is_gitignored = |path: &Path, is_dir: bool| match &gitignore {
+                Some(it) => it.is_ignored(path, is_dir),
+                None => false,
+            };
+
             let mut cache = Vec::new();
             match read_dir {
                 Ok(mut rd) => {
                     let mut cache_size = 100;
+                    let mut reporter = ProgressReporter::new(sender.clone(), identifier);
 
                     let (is_selection, selection_path) = match &selection {
                         Some(selection) => {
                             let path = path.join(selection);
-                            if path.exists() {
-                                let kind = if path.is_dir() {
-                                    ContentKind::Directory
-                                } else {
-                                    ContentKind::File
-                                };
-
-                                cache.push((kind, selection.clone()));
+                            if let Ok(metadata) = fs::symlink_metadata(&path).await {
+                                let kind = ContentKind::new(&path);
+                                let is_dir = kind == ContentKind::Directory;
+                                let metadata = Some(metadata);
+                                cache.push(DirectoryContent {
+                                    is_gitignored: is_gitignored(&path, is_dir),
+                                    ..DirectoryContent::new(
+                                        selection.clone(),
+                                        kind,
+                                        metadata.as_ref(),
+                                    )
+                                });
 
                                 (true, path)
                             } else {
@@ -327,21 +411,25 @@ async fn run_task(
                             break;
                         }
 
-                        let kind = if entry.path().is_dir() {
-                            ContentKind::Directory
-                        } else {
-                            ContentKind::File
-                        };
+                        let entry_path = entry.path();
+                        let kind = ContentKind::new(&entry_path);
+                        let is_dir = kind == ContentKind::Directory;
 
-                        let content = match entry.path().file_name() {
+                        let content = match entry_path.file_name() {
                             Some(content) => content.to_str().unwrap_or("").to_string(),
                             None => "".to_string(),
                         };
 
-                        if !is_selection || entry.path() != selection_path {
-                            cache.push((kind, content));
+                        if !is_selection || entry_path != selection_path {
+                            let metadata = fs::symlink_metadata(&entry_path).await.ok();
+                            cache.push(DirectoryContent {
+                                is_gitignored: is_gitignored(&entry_path, is_dir),
+                                ..DirectoryContent::new(content, kind, metadata.as_ref())
+                            });
                         }
 
+                        reporter.add(1, 0);
+
                         if cache.len() >= cache_size {
                             let _ = sender
                                 .send(to_envelope(vec![Message::EnumerationChanged(
@@ -361,7 +449,7 @@ async fn run_task(
 
                     let _ = sender
                         .send(to_envelope(vec![Message::EnumerationFinished(
-                            path, cache, selection,
+                            path, cache, selection, gitignore,
                         )]))
                         .await;
                 }
@@ -370,10 +458,40 @@ async fn run_task(
                 }
             }
         }
-        Task::ExecuteFd(base, params) => match command::fd(base.as_path(), params).await {
+        Task::ExecuteFd(base, params) => match command::fd(base.as_path(), params.clone()).await {
             Ok(paths) => {
+                let title = format!("fd {}", params);
                 let result = sender
-                    .send(to_envelope(vec![Message::FdResult(paths)]))
+                    .send(to_envelope(vec![Message::FdResult(title, paths)]))
+                    .await;
+
+                if let Err(error) = result {
+                    tracing::error!("sending message failed: {:?}", error);
+                }
+            }
+            Err(err) => {
+                emit_error(sender, err).await;
+            }
+        },
+        Task::ExecuteRg(base, pattern) => match command::rg(base.as_path(), &pattern).await {
+            Ok(entries) => {
+                let title = format!("rg {}", pattern);
+                let result = sender
+                    .send(to_envelope(vec![Message::RgResult(title, entries)]))
+                    .await;
+
+                if let Err(error) = result {
+                    tracing::error!("sending message failed: {:?}", error);
+                }
+            }
+            Err(err) => {
+                emit_error(sender, err).await;
+            }
+        },
+        Task::ExecuteShell(base, cmd) => match command::shell(base.as_path(), &cmd, remote).await {
+            Ok(content) => {
+                let result = sender
+                    .send(to_envelope(vec![Message::ShellFinished(content)]))
                     .await;
 
                 if let Err(error) = result {
@@ -398,7 +516,74 @@ async fn run_task(
                 emit_error(sender, err).await;
             }
         },
+        Task::ExportQuickFix(path, content) => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            fs::write(&path, content).await?;
+        }
+        Task::ImportQuickFix(path, base) => {
+            let content = fs::read_to_string(&path).await?;
+            let entries = qfix::from_exchange_content(&base, &content);
+            if entries.is_empty() {
+                let message = format!("no quick fix entries found in {:?}", path);
+                emit_error(sender, AppError::ExecutionFailed(message)).await;
+            } else {
+                let title = format!("cimport {}", path.to_string_lossy());
+                let result = sender
+                    .send(to_envelope(vec![Message::QuickFixImported(title, entries)]))
+                    .await;
+
+                if let Err(error) = result {
+                    tracing::error!("sending message failed: {:?}", error);
+                }
+            }
+        }
+        Task::MeasureJunkYardEntry(path) => {
+            let size = junkyard::get_size(&path, &cancellation).await;
+            let result = sender
+                .send(to_envelope(vec![Message::JunkYardEntryMeasured(
+                    path, size,
+                )]))
+                .await;
+
+            if let Err(error) = result {
+                tracing::error!("sending message failed: {:?}", error);
+            }
+        }
+        Task::LoadArchivePreview(entry) => {
+            let content = match junkyard::list(&entry) {
+                Ok(lines) => Preview::Content(entry.cache, lines),
+                Err(error) => {
+                    tracing::error!("listing archive failed: {:?}", error);
+                    Preview::None(entry.cache)
+                }
+            };
+
+            sender
+                .send(to_envelope(vec![Message::PreviewLoaded(content)]))
+                .await?;
+        }
         Task::LoadPreview(path, rect) => {
+            if !path.exists() {
+                let content = match fs::read_link(&path).await {
+                    Ok(target) => Preview::Content(
+                        path.clone(),
+                        vec![format!(
+                            "\x1b[91mbroken link\x1b[0m -> {}",
+                            target.display()
+                        )],
+                    ),
+                    Err(_) => Preview::None(path.clone()),
+                };
+
+                sender
+                    .send(to_envelope(vec![Message::PreviewLoaded(content)]))
+                    .await?;
+
+                return Ok(());
+            }
+
             let mime = if let Some(mime) = infer::get_from_path(&path)? {
                 let kind = mime.mime_type().split('/').collect::<Vec<_>>();
                 if kind.len() != 2 {
@@ -432,23 +617,103 @@ async fn run_task(
                 tracing::error!("sending message failed: {:?}", error);
             }
         }
-        Task::RenamePath(old, new) => {
-            if !old.exists() || new.exists() {
+        Task::RenamePath(old, new, policy) => {
+            if !exists(&old) || is_target_invalid(&old, &new, &policy) {
                 return Err(AppError::InvalidTargetPath);
             }
 
+            let task = || Task::RenamePath(old.clone(), new.clone(), policy.clone());
+            let new = match conflict::resolve(sender, &new, &policy, task).await? {
+                Some(it) => it,
+                None => return Ok(()),
+            };
+
             fs::rename(old, new).await?;
         }
-        Task::RestorePath(entry, path) => {
-            restore(entry, path)?;
+        Task::RestorePath(entry, path, policy) => {
+            let target = match entry.target.file_name() {
+                Some(name) => path.join(name),
+                None => return Err(AppError::InvalidTargetPath),
+            };
+
+            let task = || Task::RestorePath(entry.clone(), path.clone(), policy.clone());
+            let target = match conflict::resolve(sender, &target, &policy, task).await? {
+                Some(it) => it,
+                None => return Ok(()),
+            };
+
+            // NOTE: restoring to the origin recreates parents removed after trashing
+            fs::create_dir_all(&path).await?;
+
+            let mut reporter = ProgressReporter::new(sender.clone(), identifier);
+            match entry.format {
+                FileEntryFormat::Compressed => restore(entry, &target, &mut reporter)?,
+                FileEntryFormat::Uncompressed => {
+                    copy::copy_path(&entry.cache, &target, &mut reporter, &cancellation).await?
+                }
+            }
+        }
+        Task::Sequence(tasks) => {
+            let intermediates = get_intermediate_paths(&tasks);
+
+            let mut result = Ok(());
+            for task in tasks {
+                if cancellation.is_cancelled() {
+                    break;
+                }
+
+                result = Box::pin(run_task(
+                    sender,
+                    resolver.clone(),
+                    highlighter.clone(),
+                    picker.clone(),
+                    remote,
+                    task,
+                    cancellation.clone(),
+                ))
+                .await;
+
+                if result.is_err() {
+                    break;
+                }
+            }
+
+            // NOTE: stopped sequences or skipped renames leave intermediate paths behind
+            let leftovers: Vec<_> = intermediates
+                .iter()
+                .filter(|path| exists(path))
+                .map(|path| path.display().to_string())
+                .collect();
+
+            if !leftovers.is_empty() {
+                let message = format!("temporary paths left over: {}", leftovers.join(", "));
+                let _ = sender
+                    .send(to_envelope(vec![Message::Error(message)]))
+                    .await;
+            }
+
+            result?;
         }
         Task::TrashPath(entry) => {
-            if let Err(error) = cache_and_compress(entry).await {
+            let cache = entry.cache.clone();
+            let mut reporter = ProgressReporter::new(sender.clone(), identifier);
+            let result = match entry.backend {
+                TrashBackend::Freedesktop => trash::trash(&entry).await,
+                TrashBackend::JunkYard => junkyard::trash(entry, &mut reporter).await,
+            };
+
+            if let Err(error) = result {
                 emit_error(sender, error).await;
+
+                // NOTE: otherwise the entry would stay processing in the junk yard forever
+                let _ = sender
+                    .send(to_envelope(vec![Message::PathRemoved(cache)]))
+                    .await;
             }
         }
         Task::YankPath(entry) => {
-            if let Err(error) = compress(entry).await {
+            let mut reporter = ProgressReporter::new(sender.clone(), identifier);
+            if let Err(error) = compress(entry, &mut reporter).await {
                 emit_error(sender, error).await;
             }
         }
@@ -501,6 +766,50 @@ async fn emit_error(sender: &Sender<Envelope>, error: AppError) {
     let _ = sender.send(to_envelope(vec![Message::Error(error)])).await;
 }
 
+// NOTE: targets of renames which are moved again later in the sequence
+fn get_intermediate_paths(tasks: &[Task]) -> Vec<PathBuf> {
+    tasks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, task)| match task {
+            Task::RenamePath(_, target, _) => tasks[index + 1..]
+                .iter()
+                .any(|task| matches!(task, Task::RenamePath(source, _, _) if source == target))
+                .then(|| target.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+// NOTE: must be checked before resolving conflicts, because overwriting removes the target
+fn is_target_invalid(source: &Path, target: &Path, policy: &ConflictPolicy) -> bool {
+    let is_within_source = source != target && target.starts_with(source);
+    let is_overwriting_source = policy == &ConflictPolicy::Overwrite && source.starts_with(target);
+
+    is_within_source || is_overwriting_source
+}
+
+pub async fn delete_path(path: &Path) -> Result<(), AppError> {
+    // NOTE: symlink_metadata to remove links itself instead of their targets
+    let metadata = match fs::symlink_metadata(path).await {
+        Ok(it) => it,
+        Err(_) => return Err(AppError::InvalidTargetPath),
+    };
+
+    if metadata.is_dir() {
+        fs::remove_dir_all(path).await?;
+    } else {
+        fs::remove_file(path).await?;
+    }
+
+    Ok(())
+}
+
+// NOTE: Path::exists follows symlinks and reports broken links as missing
+fn exists(path: &Path) -> bool {
+    path.symlink_metadata().is_ok()
+}
+
 fn to_envelope(messages: Vec<Message>) -> Envelope {
     Envelope {
         messages,
@@ -508,3 +817,104 @@ fn to_envelope(messages: Vec<Message>) -> Envelope {
         source: MessageSource::Task,
     }
 }
+
+mod test {
+    #[tokio::test]
+    async fn run_task_keeps_target_within_source() {
+        use std::{fs, sync::Arc};
+
+        use syntect::{highlighting::ThemeSet, parsing::SyntaxSet};
+        use tokio::sync::{mpsc, Mutex};
+        use tokio_util::sync::CancellationToken;
+        use yeet_keymap::MessageResolver;
+
+        use crate::{error::AppError, model::conflict::ConflictPolicy, task::Task};
+
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let source = directory.path().join("a");
+        let nested = source.join("a");
+        fs::create_dir_all(&nested).expect("create nested");
+        fs::write(nested.join("file"), "content").expect("write file");
+
+        let (sender, _receiver) = mpsc::channel(8);
+        let tasks = vec![
+            Task::CopyPath(source.clone(), nested.clone(), ConflictPolicy::Overwrite),
+            Task::RenamePath(source.clone(), nested.clone(), ConflictPolicy::Overwrite),
+            Task::CopyPath(source.clone(), nested.clone(), ConflictPolicy::Rename),
+        ];
+
+        for task in tasks {
+            let result = super::run_task(
+                &sender,
+                Arc::new(Mutex::new(MessageResolver::default())),
+                Arc::new(Mutex::new((SyntaxSet::default(), ThemeSet::default()))),
+                Arc::new(Mutex::new(None)),
+                None,
+                task,
+                CancellationToken::new(),
+            )
+            .await;
+
+            assert!(matches!(result, Err(AppError::InvalidTargetPath)));
+            assert!(nested.join("file").exists());
+        }
+    }
+
+    #[tokio::test]
+    async fn run_task_reports_leftover_intermediates() {
+        use std::{fs, sync::Arc};
+
+        use syntect::{highlighting::ThemeSet, parsing::SyntaxSet};
+        use tokio::sync::{mpsc, Mutex};
+        use tokio_util::sync::CancellationToken;
+        use yeet_keymap::MessageResolver;
+
+        use crate::{event::Message, model::conflict::ConflictPolicy, task::Task};
+
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let source = directory.path().join("a");
+        let temporary = directory.path().join(".a.bulkrename1");
+        let target = directory.path().join("b");
+        fs::write(&source, "a").expect("write source");
+        fs::write(&target, "b").expect("write target");
+
+        let (sender, mut receiver) = mpsc::channel(8);
+        let task = Task::Sequence(vec![
+            Task::RenamePath(source.clone(), temporary.clone(), ConflictPolicy::Skip),
+            Task::RenamePath(temporary.clone(), target.clone(), ConflictPolicy::Skip),
+        ]);
+
+        let result = super::run_task(
+            &sender,
+            Arc::new(Mutex::new(MessageResolver::default())),
+            Arc::new(Mutex::new((SyntaxSet::default(), ThemeSet::default()))),
+            Arc::new(Mutex::new(None)),
+            None,
+            task,
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert!(temporary.exists());
+
+        drop(sender);
+
+        let mut errors = Vec::new();
+        while let Some(envelope) = receiver.recv().await {
+            for message in envelope.messages {
+                if let Message::Error(error) = message {
+                    errors.push(error);
+                }
+            }
+        }
+
+        assert_eq!(
+            vec![format!(
+                "temporary paths left over: {}",
+                temporary.display()
+            )],
+            errors
+        );
+    }
+}
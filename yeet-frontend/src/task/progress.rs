@@ -0,0 +1,67 @@
+use std::{
+    io::Read,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::mpsc::Sender;
+
+use crate::event::{Envelope, Message, TaskProgress};
+
+use super::to_envelope;
+
+const REPORT_INTERVAL: Duration = Duration::from_millis(250);
+
+pub struct ProgressReporter {
+    identifier: String,
+    last_report: Instant,
+    pub progress: TaskProgress,
+    sender: Sender<Envelope>,
+}
+
+impl ProgressReporter {
+    pub fn new(sender: Sender<Envelope>, identifier: String) -> Self {
+        Self {
+            identifier,
+            last_report: Instant::now(),
+            progress: TaskProgress::default(),
+            sender,
+        }
+    }
+
+    pub fn add(&mut self, entries: usize, bytes: u64) {
+        self.progress.entries += entries;
+        self.progress.bytes += bytes;
+
+        if self.last_report.elapsed() < REPORT_INTERVAL {
+            return;
+        }
+
+        self.last_report = Instant::now();
+
+        // NOTE: progress is lossy to never block io bound work on a busy channel
+        let message = Message::TaskProgress(self.identifier.clone(), self.progress.clone());
+        if let Err(err) = self.sender.try_send(to_envelope(vec![message])) {
+            tracing::trace!("skipped progress report: {:?}", err);
+        }
+    }
+}
+
+pub struct ProgressRead<'a, R> {
+    inner: R,
+    reporter: &'a mut ProgressReporter,
+}
+
+impl<'a, R> ProgressRead<'a, R> {
+    pub fn new(inner: R, reporter: &'a mut ProgressReporter) -> Self {
+        Self { inner, reporter }
+    }
+}
+
+impl<R: Read> Read for ProgressRead<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.reporter.add(0, read as u64);
+
+        Ok(read)
+    }
+}
@@ -0,0 +1,214 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use yeet_keymap::message::{KeymapMessage, PrintContent};
+
+use crate::{
+    action::{self, Action},
+    event::Message,
+    model::{conflict::ConflictPolicy, Model},
+    task::Task,
+};
+
+pub fn edit(model: &Model, paths: Vec<PathBuf>) -> Vec<Action> {
+    if paths.is_empty() {
+        let message = "bulkrename requires at least one path".to_owned();
+        return vec![Action::EmitMessages(vec![Message::Error(message)])];
+    }
+
+    vec![Action::BulkRename(model.files.current.path.clone(), paths)]
+}
+
+pub fn apply(model: &mut Model) -> Vec<Action> {
+    match model.bulkrename.take() {
+        Some(tasks) => vec![Action::Task(Task::Sequence(tasks))],
+        None => {
+            let message = "no pending bulkrename to apply".to_owned();
+            vec![Action::EmitMessages(vec![Message::Error(message)])]
+        }
+    }
+}
+
+pub fn plan(model: &mut Model, base: &Path, sources: &[PathBuf], lines: &[String]) -> Vec<Action> {
+    let tasks = match get_tasks(base, sources, lines) {
+        Ok(it) => it,
+        Err(err) => return vec![Action::EmitMessages(vec![Message::Error(err)])],
+    };
+
+    if tasks.is_empty() {
+        model.bulkrename = None;
+
+        let content = vec![PrintContent::Information(
+            "bulkrename found no changes".to_owned(),
+        )];
+        return vec![action::emit_keymap(KeymapMessage::Print(content))];
+    }
+
+    let mut content = vec![PrintContent::Default(":bulkrename".to_owned())];
+    for task in tasks.iter() {
+        let line = match task {
+            Task::DeletePath(path) => format!("delete {}", path.display()),
+            Task::RenamePath(old, new, _) => {
+                format!("rename {} -> {}", old.display(), new.display())
+            }
+            _ => unreachable!(),
+        };
+        content.push(PrintContent::Default(line));
+    }
+    content.push(PrintContent::Information(
+        "apply with :bulkrename!".to_owned(),
+    ));
+
+    model.bulkrename = Some(tasks);
+
+    vec![action::emit_keymap(KeymapMessage::Print(content))]
+}
+
+pub fn to_line(base: &Path, path: &Path) -> String {
+    match path.strip_prefix(base) {
+        Ok(relative) => relative.to_string_lossy().to_string(),
+        Err(_) => path.to_string_lossy().to_string(),
+    }
+}
+
+fn get_tasks(base: &Path, sources: &[PathBuf], lines: &[String]) -> Result<Vec<Task>, String> {
+    if sources.len() != lines.len() {
+        return Err(format!(
+            "bulkrename expects {} lines, got {}",
+            sources.len(),
+            lines.len()
+        ));
+    }
+
+    let mut deletes = Vec::new();
+    let mut renames = Vec::new();
+    let mut freed = HashSet::new();
+    for (source, line) in sources.iter().zip(lines) {
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() {
+            deletes.push(Task::DeletePath(source.clone()));
+            freed.insert(source);
+            continue;
+        }
+
+        let target = base.join(line);
+        if &target != source {
+            renames.push((source.clone(), target));
+            freed.insert(source);
+        }
+    }
+
+    let mut targets = HashSet::new();
+    for (_, target) in renames.iter() {
+        if !targets.insert(target) {
+            return Err(format!(
+                "bulkrename target {} is not unique",
+                target.display()
+            ));
+        }
+
+        // NOTE: only deleted or renamed sources free their path, unchanged sources keep it
+        let is_occupied = sources.contains(target) || target.symlink_metadata().is_ok();
+        if !freed.contains(target) && is_occupied {
+            return Err(format!("bulkrename target {} exists", target.display()));
+        }
+    }
+
+    let mut tasks = deletes;
+    let mut temporary_count = 0;
+    while !renames.is_empty() {
+        // NOTE: a rename is blocked while its target is still a source of a pending rename
+        let unblocked = renames
+            .iter()
+            .position(|(_, target)| !renames.iter().any(|(source, _)| source == target));
+
+        match unblocked {
+            Some(index) => {
+                let (source, target) = renames.remove(index);
+                tasks.push(Task::RenamePath(source, target, ConflictPolicy::Skip));
+            }
+            None => {
+                let (source, _) = &mut renames[0];
+                let temporary = get_temporary_path(source, &mut temporary_count);
+                tasks.push(Task::RenamePath(
+                    source.clone(),
+                    temporary.clone(),
+                    ConflictPolicy::Skip,
+                ));
+
+                *source = temporary;
+            }
+        }
+    }
+
+    Ok(tasks)
+}
+
+fn get_temporary_path(path: &Path, count: &mut usize) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    loop {
+        *count += 1;
+
+        let temporary = path.with_file_name(format!(".{}.bulkrename{}", name, count));
+        if temporary.symlink_metadata().is_err() {
+            return temporary;
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn get_tasks_resolves_swaps() {
+        use std::path::{Path, PathBuf};
+
+        use crate::{model::conflict::ConflictPolicy, task::Task};
+
+        let base = Path::new("/yeet-bulkrename-test");
+        let sources: Vec<PathBuf> = ["a", "b", "c", "d"].iter().map(|n| base.join(n)).collect();
+        let lines: Vec<String> = ["b", "a", "", "e"].iter().map(|l| l.to_string()).collect();
+
+        let tasks = super::get_tasks(base, &sources, &lines).expect("valid tasks");
+        let temporary = base.join(".a.bulkrename1");
+
+        assert_eq!(
+            tasks,
+            vec![
+                Task::DeletePath(base.join("c")),
+                Task::RenamePath(base.join("d"), base.join("e"), ConflictPolicy::Skip),
+                Task::RenamePath(base.join("a"), temporary.clone(), ConflictPolicy::Skip),
+                Task::RenamePath(base.join("b"), base.join("a"), ConflictPolicy::Skip),
+                Task::RenamePath(temporary, base.join("b"), ConflictPolicy::Skip),
+            ]
+        );
+
+        // NOTE: b stays unchanged, thus it is not freed for a
+        let lines: Vec<String> = ["b", "b", "c", "d"].iter().map(|l| l.to_string()).collect();
+        assert_eq!(
+            Err(format!(
+                "bulkrename target {} exists",
+                base.join("b").display()
+            )),
+            super::get_tasks(base, &sources, &lines)
+        );
+    }
+
+    #[test]
+    fn get_tasks_rejects_invalid_edits() {
+        use std::path::{Path, PathBuf};
+
+        let base = Path::new("/yeet-bulkrename-test");
+        let sources: Vec<PathBuf> = ["a", "b"].iter().map(|n| base.join(n)).collect();
+
+        let lines = vec!["a".to_owned()];
+        assert!(super::get_tasks(base, &sources, &lines).is_err());
+
+        let lines = vec!["c".to_owned(), "c".to_owned()];
+        assert!(super::get_tasks(base, &sources, &lines).is_err());
+    }
+}
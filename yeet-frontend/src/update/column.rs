@@ -0,0 +1,165 @@
+use std::{collections::HashMap, time::SystemTime};
+
+use chrono::{DateTime, Local};
+use yeet_buffer::model::BufferLine;
+
+use crate::{
+    event::{to_human_readable_size, ContentKind, DirectoryContent},
+    settings::Settings,
+};
+
+pub fn set_columns(
+    settings: &Settings,
+    owners: &mut HashMap<u32, Option<String>>,
+    line: &mut BufferLine,
+    content: &DirectoryContent,
+) {
+    let mut columns = Vec::new();
+    if settings.show_permissions {
+        columns.push(get_permissions(content));
+    }
+
+    if settings.show_owner {
+        // NOTE: user lookups can hit nss or ldap, thus names get resolved once per uid
+        let owner = content
+            .uid
+            .and_then(|uid| owners.entry(uid).or_insert_with(|| get_owner(uid)).clone());
+        let owner = owner.as_deref().unwrap_or("-");
+        columns.push(format!("{:<8}", owner.chars().take(8).collect::<String>()));
+    }
+
+    if settings.show_size {
+        columns.push(format!("{:>5}", get_size(content)));
+    }
+
+    if settings.show_mtime {
+        columns.push(format!("{:<16}", get_modified(content)));
+    }
+
+    let link = match &content.kind {
+        ContentKind::Symlink { target, .. } => Some(format!("-> {}", target.display())),
+        _ => None,
+    };
+
+    line.suffix = match (link, columns.is_empty()) {
+        (None, true) => None,
+        (None, false) => Some(format!("\x1b[90m {}\x1b[0m", columns.join(" "))),
+        (Some(link), true) => Some(format!("\x1b[90m {}\x1b[0m", link)),
+        (Some(link), false) => Some(format!("\x1b[90m {}  {}\x1b[0m", link, columns.join(" "))),
+    };
+}
+
+fn get_modified(content: &DirectoryContent) -> String {
+    match content.modified {
+        Some(modified) => format_time(modified),
+        None => "-".to_owned(),
+    }
+}
+
+pub fn format_time(time: SystemTime) -> String {
+    DateTime::<Local>::from(time)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+#[cfg(unix)]
+fn get_owner(uid: u32) -> Option<String> {
+    uzers::get_user_by_uid(uid).map(|user| user.name().to_string_lossy().to_string())
+}
+
+#[cfg(not(unix))]
+fn get_owner(_uid: u32) -> Option<String> {
+    None
+}
+
+fn get_permissions(content: &DirectoryContent) -> String {
+    let mode = match content.permissions {
+        Some(it) => it,
+        None => return "-".repeat(10),
+    };
+
+    let kind = match content.kind {
+        ContentKind::Directory => 'd',
+        ContentKind::File => '-',
+        ContentKind::Symlink { .. } => 'l',
+    };
+
+    let flags = ['r', 'w', 'x'];
+    let mut permissions = String::from(kind);
+    for shift in (0..9).rev() {
+        if mode & (1 << shift) != 0 {
+            permissions.push(flags[2 - shift % 3]);
+        } else {
+            permissions.push('-');
+        }
+    }
+
+    permissions
+}
+
+fn get_size(content: &DirectoryContent) -> String {
+    if content.kind == ContentKind::Directory {
+        return "-".to_owned();
+    }
+
+    to_human_readable_size(content.size)
+}
+
+mod test {
+    #[test]
+    fn set_columns() {
+        use std::collections::HashMap;
+
+        use yeet_buffer::model::BufferLine;
+
+        use crate::{
+            event::{ContentKind, DirectoryContent},
+            settings::Settings,
+        };
+
+        let mut content = DirectoryContent::new("file".to_owned(), ContentKind::File, None);
+        content.uid = Some(u32::MAX);
+        content.permissions = Some(0o100754);
+        content.size = 4300;
+
+        let mut settings = Settings::default();
+        let mut owners = HashMap::new();
+        let mut line = BufferLine::default();
+
+        super::set_columns(&settings, &mut owners, &mut line, &content);
+        assert_eq!(line.suffix, None);
+
+        settings.show_permissions = true;
+        settings.show_owner = true;
+        settings.show_size = true;
+
+        super::set_columns(&settings, &mut owners, &mut line, &content);
+        assert_eq!(
+            line.suffix.as_deref(),
+            Some("\x1b[90m -rwxr-xr-- -         4.2K\x1b[0m")
+        );
+        assert_eq!(Some(&None), owners.get(&u32::MAX));
+
+        content.size = 1023 * 1024;
+
+        super::set_columns(&settings, &mut owners, &mut line, &content);
+        assert_eq!(
+            line.suffix.as_deref(),
+            Some("\x1b[90m -rwxr-xr-- -        1023K\x1b[0m")
+        );
+
+        content.size = 4300;
+
+        settings.show_owner = false;
+        content.kind = ContentKind::Symlink {
+            is_broken: false,
+            target: "target".into(),
+        };
+
+        super::set_columns(&settings, &mut owners, &mut line, &content);
+        assert_eq!(
+            line.suffix.as_deref(),
+            Some("\x1b[90m -> target  lrwxr-xr--  4.2K\x1b[0m")
+        );
+    }
+}
@@ -5,16 +5,35 @@ use yeet_keymap::message::KeymapMessage;
 use crate::{
     action::{self, Action},
     event::Message,
-    model::{mark::Marks, Model},
+    model::{conflict::ConflictPolicy, mark::Marks, Model},
     task::Task,
+    update::{bulkrename, selection},
 };
 
-pub fn copy(model: &Model, target: &str) -> Vec<Action> {
+pub fn bulkrename(model: &Model) -> Vec<Action> {
+    let buffer = &model.files.current.buffer;
+    if !buffer.undo.get_uncommited_changes().is_empty() {
+        let message = "bulkrename requires the current buffer to be saved".to_owned();
+        return vec![Action::EmitMessages(vec![Message::Error(message)])];
+    }
+
+    let paths = buffer
+        .lines
+        .iter()
+        .map(|line| line.content.to_stripped_string())
+        .filter(|name| !name.is_empty())
+        .map(|name| model.files.current.path.join(name))
+        .collect();
+
+    bulkrename::edit(model, paths)
+}
+
+pub fn copy(model: &Model, target: &str, policy: ConflictPolicy) -> Vec<Action> {
     let mut actions = Vec::new();
-    if let Some(path) = &model.files.preview.resolve_path() {
+    for path in get_selected_paths(model) {
         tracing::info!("copying path: {:?}", path);
-        match get_target_file_path(&model.marks, target, path) {
-            Ok(target) => actions.push(Action::Task(Task::CopyPath(path.to_path_buf(), target))),
+        match get_target_file_path(&model.marks, target, &path) {
+            Ok(target) => actions.push(Action::Task(Task::CopyPath(path, target, policy.clone()))),
             Err(err) => {
                 actions.push(Action::EmitMessages(vec![Message::Error(err)]));
             }
@@ -24,24 +43,27 @@ pub fn copy(model: &Model, target: &str) -> Vec<Action> {
 }
 
 pub fn delete_selection(model: &Model) -> Vec<Action> {
+    let paths = get_selected_paths(model);
+    if paths.is_empty() {
+        tracing::warn!("deleting path failed: no path in preview set");
+    }
+
     let mut actions = Vec::new();
-    if let Some(path) = &model.files.preview.resolve_path() {
+    for path in paths {
         tracing::info!("deleting path: {:?}", path);
-        actions.push(Action::Task(Task::DeletePath(path.to_path_buf())));
-    } else {
-        tracing::warn!("deleting path failed: no path in preview set");
+        actions.push(Action::Task(Task::DeletePath(path)));
     }
 
     actions
 }
 
-pub fn rename_selection(model: &Model, target: &str) -> Vec<Action> {
+pub fn rename_selection(model: &Model, target: &str, policy: ConflictPolicy) -> Vec<Action> {
     let mut actions = Vec::new();
-    if let Some(path) = &model.files.preview.resolve_path() {
+    for path in get_selected_paths(model) {
         tracing::info!("renaming path: {:?}", path);
-        match get_target_file_path(&model.marks, target, path) {
+        match get_target_file_path(&model.marks, target, &path) {
             Ok(target) => {
-                actions.push(Action::Task(Task::RenamePath(path.to_path_buf(), target)));
+                actions.push(Action::Task(Task::RenamePath(path, target, policy.clone())));
             }
             Err(err) => {
                 actions.push(Action::EmitMessages(vec![Message::Error(err)]));
@@ -62,6 +84,18 @@ pub fn refresh(model: &Model) -> Vec<Action> {
     vec![action::emit_keymap(navigation)]
 }
 
+fn get_selected_paths(model: &Model) -> Vec<PathBuf> {
+    let range_paths = selection::get_current_selected_range_paths(model);
+    if !range_paths.is_empty() {
+        return range_paths;
+    }
+
+    match model.files.preview.resolve_path() {
+        Some(path) => vec![path.to_path_buf()],
+        None => Vec::new(),
+    }
+}
+
 fn get_target_file_path(marks: &Marks, target: &str, path: &Path) -> Result<PathBuf, String> {
     let file_name = match path.file_name() {
         Some(it) => it,
@@ -91,10 +125,56 @@ fn get_target_file_path(marks: &Marks, target: &str, path: &Path) -> Result<Path
         PathBuf::from(path)
     };
 
-    let target_file = target.join(file_name);
-    if target.is_dir() && target.exists() && !target_file.exists() {
+    // NOTE: existing target files are resolved with the conflict policy of the task
+    if target.is_dir() {
         Ok(target.join(file_name))
     } else {
         Err("target path is not valid".to_string())
     }
 }
+
+mod test {
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn delete_selection_with_broken_link() {
+        use yeet_buffer::model::{ansi::Ansi, BufferLine, Cursor};
+
+        use crate::{action::Action, model::Model, task::Task, update::selection};
+
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let base = directory.path();
+
+        let link = base.join("link");
+        std::os::unix::fs::symlink(base.join("missing"), &link).expect("create link");
+
+        let mut model = Model::default();
+        model.files.current.path = base.to_path_buf();
+        model.files.current.buffer.lines = vec![BufferLine {
+            content: Ansi::new("link"),
+            ..Default::default()
+        }];
+        model.files.current_cursor = Some(Cursor {
+            anchor: Some(0),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            Some(link.clone()),
+            selection::get_current_selected_path(&model)
+        );
+
+        let actions = super::delete_selection(&model);
+        assert_eq!(1, actions.len());
+
+        let path = match &actions[0] {
+            Action::Task(Task::DeletePath(path)) => path.clone(),
+            _ => panic!("expected delete task"),
+        };
+        assert_eq!(link, path);
+
+        crate::task::delete_path(&path)
+            .await
+            .expect("delete broken link");
+        assert!(link.symlink_metadata().is_err());
+    }
+}
@@ -4,13 +4,16 @@ use yeet_keymap::message::{KeymapMessage, QuitMode};
 use crate::{
     action::{self, Action},
     event::Message,
-    model::Model,
+    model::{conflict::ConflictPolicy, Model},
     task::Task,
+    update::{bulkrename, conflict, junkbrowser, junkyard, open, qfixeditor},
 };
 
 mod file;
 mod print;
 mod qfix;
+mod settings;
+pub mod shell;
 mod task;
 
 #[tracing::instrument(skip(model))]
@@ -27,14 +30,48 @@ pub fn execute(cmd: &str, model: &mut Model) -> Vec<Action> {
 
     // NOTE: all file commands like e.g. d! should use preview path as target to enable cdo
     match cmd_with_args {
+        (shell, _) if shell.starts_with("!!") => {
+            add_change_mode(mode_before, mode, shell::execute(model, &cmd[2..], true))
+        }
+        (shell, _) if shell.starts_with('!') => {
+            add_change_mode(mode_before, mode, shell::execute(model, &cmd[1..], false))
+        }
+        ("bulkrename", "") => add_change_mode(mode_before, mode, file::bulkrename(model)),
+        ("bulkrename!", "") => add_change_mode(mode_before, mode, bulkrename::apply(model)),
+        ("cbulkrename", "") => add_change_mode(mode_before, mode, qfix::bulkrename(model)),
+        ("cclose", "") => add_change_mode(mode_before, mode, qfixeditor::close(model)),
         ("cdo", command) => add_change_mode(mode_before, mode, qfix::cdo(model, command)),
+        ("cexport", target) if !target.is_empty() => {
+            add_change_mode(mode_before, mode, qfix::export(model, target, false))
+        }
+        ("cexport0", target) if !target.is_empty() => {
+            add_change_mode(mode_before, mode, qfix::export(model, target, true))
+        }
         ("cfirst", "") => add_change_mode(mode_before, mode, qfix::select_first(model)),
+        ("chistory", "") => print::qfix_history(&model.qfix),
+        ("chistory", number) => qfix::select_list(model, number, true),
+        ("cimport", target) if !target.is_empty() => {
+            add_change_mode(mode_before, mode, qfix::import(model, target))
+        }
         ("cl", "") => print::qfix(&model.qfix),
         ("clearcl", "") => add_change_mode(mode_before, mode, qfix::reset(model)),
         ("clearcl", path) => add_change_mode(mode_before, mode, qfix::clear_in(model, path)),
+        ("clist", "") => print::qfix(&model.qfix),
+        ("clist", number) => qfix::select_list(model, number, false),
         ("cn", "") => add_change_mode(mode_before, mode, qfix::next(model)),
         ("cN", "") => add_change_mode(mode_before, mode, qfix::previous(model)),
-        ("cp", target) => add_change_mode(mode_before, mode, file::copy(model, target)),
+        ("conflict", policy) => {
+            add_change_mode(mode_before, mode, conflict::resolve(model, policy))
+        }
+        ("copen", "") => add_change_mode(mode_before, Mode::Normal, qfixeditor::open(model)),
+        ("cp", target) => {
+            let policy = model.settings.conflict_policy.clone();
+            add_change_mode(mode_before, mode, file::copy(model, target, policy))
+        }
+        ("cp!", target) => {
+            let policy = ConflictPolicy::Overwrite;
+            add_change_mode(mode_before, mode, file::copy(model, target, policy))
+        }
         ("d!", "") => add_change_mode(mode_before, mode, file::delete_selection(model)),
         ("delm", args) if !args.is_empty() => {
             let mut marks = Vec::new();
@@ -70,8 +107,43 @@ pub fn execute(cmd: &str, model: &mut Model) -> Vec<Action> {
         ),
         ("invertcl", "") => add_change_mode(mode_before, mode, qfix::invert_in_current(model)),
         ("junk", "") => print::junkyard(&model.junk),
+        ("junkyard", "") => {
+            add_change_mode(mode_before, Mode::Navigation, junkbrowser::open(model))
+        }
+        ("junkclean", "") => add_change_mode(mode_before, mode, junkyard::clean_junkyard(model)),
+        ("junkrestore", register) if register.chars().count() <= 1 => {
+            let register = register.chars().next().unwrap_or('"');
+            add_change_mode(
+                mode_before,
+                mode,
+                junkyard::restore_from_junkyard(model, &register),
+            )
+        }
+        ("junkrestore", index) if index.parse::<usize>().is_ok_and(|it| it > 0) => {
+            let index = index.parse::<usize>().unwrap_or(1);
+            add_change_mode(
+                mode_before,
+                mode,
+                junkyard::restore_trash_from_junkyard(model, index - 1),
+            )
+        }
         ("marks", "") => print::marks(&model.marks),
-        ("mv", target) => add_change_mode(mode_before, mode, file::rename_selection(model, target)),
+        ("mv", target) => {
+            let policy = model.settings.conflict_policy.clone();
+            add_change_mode(
+                mode_before,
+                mode,
+                file::rename_selection(model, target, policy),
+            )
+        }
+        ("mv!", target) => {
+            let policy = ConflictPolicy::Overwrite;
+            add_change_mode(
+                mode_before,
+                mode,
+                file::rename_selection(model, target, policy),
+            )
+        }
         ("noh", "") => add_change_mode(
             mode_before,
             mode,
@@ -79,11 +151,27 @@ pub fn execute(cmd: &str, model: &mut Model) -> Vec<Action> {
                 KeymapMessage::ClearSearchHighlight,
             )])],
         ),
+        ("openwith", command) if !command.is_empty() => {
+            add_change_mode(mode_before, mode, open::open_selected_with(model, command))
+        }
         ("q", "") => vec![action::emit_keymap(KeymapMessage::Quit(
             QuitMode::FailOnRunningTasks,
         ))],
         ("q!", "") => vec![action::emit_keymap(KeymapMessage::Quit(QuitMode::Force))],
         ("reg", "") => print::register(&model.register),
+        ("rg", pattern) if !pattern.is_empty() => add_change_mode(
+            mode_before,
+            mode,
+            vec![Action::Task(Task::ExecuteRg(
+                model.files.current.path.clone(),
+                pattern.to_owned(),
+            ))],
+        ),
+        ("set", args) if !args.is_empty() => {
+            add_change_mode(mode_before, mode, settings::set(model, args))
+        }
+        ("sort", args) => add_change_mode(mode_before, mode, settings::sort(model, args, false)),
+        ("sort!", args) => add_change_mode(mode_before, mode, settings::sort(model, args, true)),
         ("tl", "") => print::tasks(&model.current_tasks),
         ("w", "") => add_change_mode(
             mode_before,
@@ -143,7 +231,7 @@ fn get_mode_after_command(mode_before: &Option<Mode>) -> Mode {
         match mode {
             Mode::Command(_) => Mode::default(),
             Mode::Insert | Mode::Normal => Mode::Normal,
-            Mode::Navigation => Mode::Navigation,
+            Mode::Navigation | Mode::VisualLine => Mode::Navigation,
         }
     } else {
         Mode::default()
@@ -167,6 +255,10 @@ mod test {
         let result = super::get_mode_after_command(&mode_before);
         assert_eq!(result, Mode::Navigation);
 
+        let mode_before = Some(Mode::VisualLine);
+        let result = super::get_mode_after_command(&mode_before);
+        assert_eq!(result, Mode::Navigation);
+
         let mode_before = None;
         let result = super::get_mode_after_command(&mode_before);
         assert_eq!(result, Mode::Navigation);
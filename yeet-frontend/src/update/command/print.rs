@@ -36,10 +36,19 @@ pub fn marks(marks: &Marks) -> Vec<Action> {
 }
 
 pub fn tasks(tasks: &HashMap<String, CurrentTask>) -> Vec<Action> {
-    let mut contents = vec![":tl".to_string(), "Id   Task".to_string()];
+    let mut contents = vec![
+        ":tl".to_string(),
+        format!("{:<4} {:<24} Task", "Id", "Progress"),
+    ];
     let mut tasks: Vec<_> = tasks
         .values()
-        .map(|task| format!("{:<4} {}", task.id, task.external_id))
+        .map(|task| {
+            let progress = match &task.progress {
+                Some(progress) => progress.to_string(),
+                None => "".to_string(),
+            };
+            format!("{:<4} {:<24} {}", task.id, progress, task.external_id)
+        })
         .collect();
 
     tasks.sort();
@@ -54,14 +63,15 @@ pub fn tasks(tasks: &HashMap<String, CurrentTask>) -> Vec<Action> {
 }
 
 pub fn qfix(qfix: &QuickFix) -> Vec<Action> {
+    let qfix = qfix.current();
     let max_width = (qfix.entries.len() + 1).to_string().len();
 
     let entries: Vec<_> = qfix
         .entries
         .iter()
         .enumerate()
-        .map(|(i, path)| (i + 1, path.to_string_lossy().to_string()))
-        .map(|(i, path)| format!("{:>max_width$} {}", i, path))
+        .map(|(i, entry)| (i + 1, entry.to_string()))
+        .map(|(i, entry)| format!("{:>max_width$} {}", i, entry))
         .collect();
 
     let mut contents = vec![":cl".to_string()];
@@ -86,6 +96,28 @@ pub fn qfix(qfix: &QuickFix) -> Vec<Action> {
     vec![action::emit_keymap(KeymapMessage::Print(content))]
 }
 
+pub fn qfix_history(qfix: &QuickFix) -> Vec<Action> {
+    let max_width = (qfix.lists.len() + 1).to_string().len();
+
+    let mut contents = vec![PrintContent::Default(":chistory".to_string())];
+    for (index, list) in qfix.lists.iter().enumerate() {
+        let content = format!(
+            "{:>max_width$} {} ({} entries)",
+            index + 1,
+            list.title,
+            list.entries.len()
+        );
+
+        if index == qfix.current_list {
+            contents.push(PrintContent::Information(content));
+        } else {
+            contents.push(PrintContent::Default(content));
+        }
+    }
+
+    vec![action::emit_keymap(KeymapMessage::Print(contents))]
+}
+
 pub fn junkyard(junkyard: &JunkYard) -> Vec<Action> {
     let mut contents = vec![":junk".to_string(), "Name Content".to_string()];
     if let Some(current) = get_junkyard_transaction(junkyard, &'"') {
@@ -4,16 +4,40 @@ use yeet_keymap::message::{KeymapMessage, PrintContent};
 
 use crate::{
     action::{self, Action},
+    init,
     model::{
-        qfix::{CdoState, QFIX_SIGN_ID},
+        qfix::{CdoState, QuickFixEntry, QFIX_SIGN_ID},
         Model,
     },
-    update::sign,
+    task::Task,
+    update::{bulkrename, qfix, sign},
 };
 
+use super::print;
+
+pub fn bulkrename(model: &Model) -> Vec<Action> {
+    bulkrename::edit(model, model.qfix.current().paths())
+}
+
+pub fn export(model: &Model, target: &str, nul_separated: bool) -> Vec<Action> {
+    let path = model.files.current.path.join(target);
+    let entries = &model.qfix.current().entries;
+    let content = init::qfix::to_exchange_content(entries, nul_separated);
+
+    vec![Action::Task(Task::ExportQuickFix(path, content))]
+}
+
+pub fn import(model: &Model, target: &str) -> Vec<Action> {
+    let base = model.files.current.path.clone();
+    let path = base.join(target);
+
+    vec![Action::Task(Task::ImportQuickFix(path, base))]
+}
+
 pub fn reset(model: &mut Model) -> Vec<Action> {
-    model.qfix.entries.clear();
-    model.qfix.current_index = 0;
+    let qfix = model.qfix.current_mut();
+    qfix.entries.clear();
+    qfix.current_index = 0;
     sign::unset_sign_on_all_buffers(model, QFIX_SIGN_ID);
 
     Vec::new()
@@ -31,8 +55,9 @@ pub fn clear_in(model: &mut Model, path: &str) -> Vec<Action> {
         }
 
         let path = current_path.join(bl.content.to_stripped_string());
-        if model.qfix.entries.contains(&path) {
-            model.qfix.entries.retain(|p| p != &path);
+        let qfix = model.qfix.current_mut();
+        if qfix.contains(&path) {
+            qfix.remove(&path);
             sign::unset(bl, QFIX_SIGN_ID);
         }
     }
@@ -51,13 +76,14 @@ pub fn cdo(model: &mut Model, command: &str) -> Vec<Action> {
 }
 
 pub fn select_first(model: &mut Model) -> Vec<Action> {
-    model.qfix.current_index = 0;
+    let qfix = model.qfix.current_mut();
+    qfix.current_index = 0;
 
-    match model.qfix.entries.first() {
+    match qfix.entries.first() {
         Some(it) => {
-            if it.exists() {
+            if it.path.exists() {
                 vec![action::emit_keymap(KeymapMessage::NavigateToPathAsPreview(
-                    it.clone(),
+                    it.path.clone(),
                 ))]
             } else {
                 next(model)
@@ -70,9 +96,10 @@ pub fn select_first(model: &mut Model) -> Vec<Action> {
 }
 
 pub fn next(model: &mut Model) -> Vec<Action> {
-    let mut entry = model.qfix.entries.iter().enumerate().filter_map(|(i, p)| {
-        if i > model.qfix.current_index && p.exists() {
-            Some((i, p))
+    let qfix = model.qfix.current_mut();
+    let mut entry = qfix.entries.iter().enumerate().filter_map(|(i, p)| {
+        if i > qfix.current_index && p.path.exists() {
+            Some((i, p.path.clone()))
         } else {
             None
         }
@@ -80,9 +107,9 @@ pub fn next(model: &mut Model) -> Vec<Action> {
 
     match entry.next() {
         Some((i, p)) => {
-            model.qfix.current_index = i;
+            qfix.current_index = i;
             vec![action::emit_keymap(KeymapMessage::NavigateToPathAsPreview(
-                p.clone(),
+                p,
             ))]
         }
         None => {
@@ -94,25 +121,20 @@ pub fn next(model: &mut Model) -> Vec<Action> {
 }
 
 pub fn previous(model: &mut Model) -> Vec<Action> {
-    let mut entry = model
-        .qfix
-        .entries
-        .iter()
-        .enumerate()
-        .rev()
-        .filter_map(|(i, p)| {
-            if i < model.qfix.current_index && p.exists() {
-                Some((i, p))
-            } else {
-                None
-            }
-        });
+    let qfix = model.qfix.current_mut();
+    let mut entry = qfix.entries.iter().enumerate().rev().filter_map(|(i, p)| {
+        if i < qfix.current_index && p.path.exists() {
+            Some((i, p.path.clone()))
+        } else {
+            None
+        }
+    });
 
     match entry.next() {
         Some((i, p)) => {
-            model.qfix.current_index = i;
+            qfix.current_index = i;
             vec![action::emit_keymap(KeymapMessage::NavigateToPathAsPreview(
-                p.clone(),
+                p,
             ))]
         }
         None => {
@@ -131,14 +153,35 @@ pub fn invert_in_current(model: &mut Model) -> Vec<Action> {
         }
 
         let path = current_path.join(bl.content.to_stripped_string());
-        if model.qfix.entries.contains(&path) {
-            model.qfix.entries.retain(|p| p != &path);
+        let qfix = model.qfix.current_mut();
+        if qfix.contains(&path) {
+            qfix.remove(&path);
             sign::unset(bl, QFIX_SIGN_ID);
         } else {
-            model.qfix.entries.push(path.clone());
+            qfix.entries.push(QuickFixEntry::from(path));
             sign::set(bl, QFIX_SIGN_ID);
         }
     }
 
     Vec::new()
 }
+
+pub fn select_list(model: &mut Model, number: &str, history: bool) -> Vec<Action> {
+    let index = match number.parse::<usize>() {
+        Ok(it) if it > 0 && it <= model.qfix.lists.len() => it - 1,
+        _ => {
+            return vec![action::emit_keymap(KeymapMessage::Print(vec![
+                PrintContent::Error(format!("no quick fix list {}", number)),
+            ]))]
+        }
+    };
+
+    model.qfix.current_list = index;
+    qfix::set_signs_for_current_list(model);
+
+    if history {
+        print::qfix_history(&model.qfix)
+    } else {
+        print::qfix(&model.qfix)
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    action::Action,
+    event::Message,
+    model::{sort::SortMode, Model},
+    update::{
+        enumeration::update_on_filter_change, settings::update_with_settings, sort::get_sort_mode,
+    },
+};
+
+pub fn set(model: &mut Model, args: &str) -> Vec<Action> {
+    let (option, result) = match args.split_once('=') {
+        Some((option, value)) => {
+            let option = option.trim();
+            (option, model.settings.set(option, value.trim()))
+        }
+        None => match args.trim().strip_suffix('!') {
+            Some(option) => (option, model.settings.toggle(option)),
+            None => {
+                let err = format!("set expects option=value or option!, got '{}'", args);
+                return vec![Action::EmitMessages(vec![Message::Error(err)])];
+            }
+        },
+    };
+
+    if let Err(err) = result {
+        return vec![Action::EmitMessages(vec![Message::Error(err.to_string())])];
+    }
+
+    update_with_settings(model);
+
+    match option {
+        "ignore_patterns" | "show_hidden" | "show_mtime" | "show_owner" | "show_permissions"
+        | "show_size" | "sort" | "use_gitignore" => update_on_filter_change(model),
+        _ => Vec::new(),
+    }
+}
+
+pub fn sort(model: &mut Model, args: &str, reverse: bool) -> Vec<Action> {
+    let path = model.files.current.path.clone();
+    let sort = match (args, reverse) {
+        ("", false) => None,
+        ("", true) => {
+            let mut sort = get_sort_mode(&model.files.sorts, &model.settings, &path).clone();
+            sort.reverse = !sort.reverse;
+            Some(sort)
+        }
+        (args, reverse) => match args.parse::<SortMode>() {
+            Ok(mut it) => {
+                it.reverse |= reverse;
+                Some(it)
+            }
+            Err(err) => return vec![Action::EmitMessages(vec![Message::Error(err.to_string())])],
+        },
+    };
+
+    match sort {
+        Some(sort) => model.files.sorts.insert(path, sort),
+        None => model.files.sorts.remove(&path),
+    };
+
+    update_on_filter_change(model)
+}
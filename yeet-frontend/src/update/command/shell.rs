@@ -0,0 +1,78 @@
+use yeet_keymap::message::{KeymapMessage, PrintContent};
+
+use crate::{
+    action::{self, Action},
+    model::Model,
+    shell,
+    task::Task,
+};
+
+use super::file;
+
+pub fn execute(model: &Model, command: &str, foreground: bool) -> Vec<Action> {
+    let command = expand(model, command);
+    let base = model.files.current.path.clone();
+
+    if foreground {
+        vec![Action::Shell(base, command)]
+    } else {
+        vec![Action::Task(Task::ExecuteShell(base, command))]
+    }
+}
+
+pub fn finished(model: &Model, content: Vec<PrintContent>) -> Vec<Action> {
+    let mut actions = Vec::new();
+    if !content.is_empty() {
+        actions.push(action::emit_keymap(KeymapMessage::Print(content)));
+    }
+    actions.extend(file::refresh(model));
+    actions
+}
+
+// NOTE: %s expands to the selected path, %d to the current directory and %q to all quick fix
+// entries. Use %% for a literal percent sign.
+fn expand(model: &Model, command: &str) -> String {
+    shell::expand(command, |placeholder| match placeholder {
+        's' => Some(
+            model
+                .files
+                .preview
+                .resolve_path()
+                .map(|path| shell::quote(&path.to_string_lossy()))
+                .unwrap_or_default(),
+        ),
+        'd' => Some(shell::quote(&model.files.current.path.to_string_lossy())),
+        'q' => {
+            let paths: Vec<_> = model
+                .qfix
+                .current()
+                .paths()
+                .iter()
+                .map(|path| shell::quote(&path.to_string_lossy()))
+                .collect();
+            Some(paths.join(" "))
+        }
+        _ => None,
+    })
+}
+
+mod test {
+    #[test]
+    fn expand() {
+        use std::path::PathBuf;
+
+        use crate::model::Model;
+
+        let mut model = Model::default();
+        model.files.current.path = PathBuf::from("/home/it's");
+
+        let qfix = model.qfix.current_mut();
+        qfix.entries.push(PathBuf::from("/a").into());
+        qfix.entries.push(PathBuf::from("/b c").into());
+
+        assert_eq!(
+            "ls '/home/it'\\''s' && echo '/a' '/b c' 100% %x",
+            super::expand(&model, "ls %d && echo %q 100%% %x")
+        );
+    }
+}
@@ -20,7 +20,7 @@ use super::set_viewport_dimensions;
 pub fn update_commandline(model: &mut Model, message: Option<&BufferMessage>) -> Vec<Action> {
     let command_mode = match &model.mode {
         Mode::Command(it) => it,
-        Mode::Insert | Mode::Navigation | Mode::Normal => return Vec::new(),
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::VisualLine => return Vec::new(),
     };
 
     let commandline = &mut model.commandline;
@@ -49,7 +49,7 @@ pub fn update_commandline_on_modification(
 ) -> Vec<Action> {
     let command_mode = match &model.mode {
         Mode::Command(it) => it,
-        Mode::Insert | Mode::Navigation | Mode::Normal => return Vec::new(),
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::VisualLine => return Vec::new(),
     };
 
     let commandline = &mut model.commandline;
@@ -142,7 +142,7 @@ pub fn update_commandline_on_modification(
 pub fn update_commandline_on_execute(model: &mut Model) -> Vec<Action> {
     let command_mode = match &model.mode {
         Mode::Command(it) => it,
-        Mode::Insert | Mode::Navigation | Mode::Normal => return Vec::new(),
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::VisualLine => return Vec::new(),
     };
 
     let messages = match command_mode {
@@ -293,7 +293,7 @@ fn get_mode_after_command(mode_before: &Option<Mode>) -> Mode {
         match mode {
             Mode::Command(_) => unreachable!(),
             Mode::Insert | Mode::Normal => Mode::Normal,
-            Mode::Navigation => Mode::Navigation,
+            Mode::Navigation | Mode::VisualLine => Mode::Navigation,
         }
     } else {
         Mode::default()
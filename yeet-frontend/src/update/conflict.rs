@@ -0,0 +1,100 @@
+use yeet_keymap::message::{KeymapMessage, PrintContent};
+
+use crate::{
+    action::{self, Action},
+    event::Message,
+    model::{conflict::ConflictPolicy, Model},
+    task::Task,
+};
+
+pub fn add(model: &mut Model, task: Task) -> Vec<Action> {
+    let target = match &task {
+        Task::CopyPath(_, target, _) | Task::RenamePath(_, target, _) => target.clone(),
+        Task::RestorePath(entry, path, _) => match entry.target.file_name() {
+            Some(name) => path.join(name),
+            None => path.clone(),
+        },
+        _ => return Vec::new(),
+    };
+
+    model.conflicts.push(task);
+
+    let content = vec![PrintContent::Information(format!(
+        "{} exists, resolve {} conflict(s) with :conflict overwrite|rename|skip",
+        target.display(),
+        model.conflicts.len()
+    ))];
+
+    vec![action::emit_keymap(KeymapMessage::Print(content))]
+}
+
+pub fn resolve(model: &mut Model, policy: &str) -> Vec<Action> {
+    let policy = match policy.parse::<ConflictPolicy>() {
+        Ok(ConflictPolicy::Prompt) | Err(_) => {
+            let message = format!(
+                "conflict expects overwrite, rename or skip, got '{}'",
+                policy
+            );
+            return vec![Action::EmitMessages(vec![Message::Error(message)])];
+        }
+        Ok(it) => it,
+    };
+
+    if model.conflicts.is_empty() {
+        let message = "no pending conflicts to resolve".to_owned();
+        return vec![Action::EmitMessages(vec![Message::Error(message)])];
+    }
+
+    model
+        .conflicts
+        .drain(..)
+        .filter_map(|task| match task {
+            Task::CopyPath(source, target, _) => {
+                Some(Task::CopyPath(source, target, policy.clone()))
+            }
+            Task::RenamePath(old, new, _) => Some(Task::RenamePath(old, new, policy.clone())),
+            Task::RestorePath(entry, path, _) => {
+                Some(Task::RestorePath(entry, path, policy.clone()))
+            }
+            _ => None,
+        })
+        .map(Action::Task)
+        .collect()
+}
+
+mod test {
+    #[test]
+    fn resolve_dispatches_stored_tasks() {
+        use std::path::PathBuf;
+
+        use crate::{
+            action::Action,
+            model::{conflict::ConflictPolicy, Model},
+            task::Task,
+        };
+
+        let mut model = Model::default();
+        let source = PathBuf::from("/source");
+        let target = PathBuf::from("/target");
+
+        let task = Task::CopyPath(source.clone(), target.clone(), ConflictPolicy::Prompt);
+        let actions = super::add(&mut model, task);
+        assert_eq!(1, actions.len());
+        assert_eq!(1, model.conflicts.len());
+
+        let actions = super::resolve(&mut model, "prompt");
+        assert!(matches!(actions.as_slice(), [Action::EmitMessages(_)]));
+        assert_eq!(1, model.conflicts.len());
+
+        let actions = super::resolve(&mut model, "rename");
+        assert!(model.conflicts.is_empty());
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::Task(Task::CopyPath(from, to, ConflictPolicy::Rename))]
+                if from == &source && to == &target
+        ));
+
+        let actions = super::resolve(&mut model, "rename");
+        assert!(matches!(actions.as_slice(), [Action::EmitMessages(_)]));
+    }
+}
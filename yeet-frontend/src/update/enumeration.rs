@@ -8,13 +8,16 @@ use yeet_buffer::{
 
 use crate::{
     action::Action,
-    event::ContentKind,
+    event::{ContentKind, DirectoryContent},
     model::{DirectoryBufferState, Model, WindowType},
     update::{
+        column::set_columns,
         cursor::{set_cursor_index_to_selection, set_cursor_index_with_history},
+        filter::{get_ignore_patterns, is_visible},
         history::get_selection_from_history,
         selection,
         sign::{set_sign_if_marked, set_sign_if_qfix},
+        sort::{get_sort_mode, sort_buffer, sort_directory},
     },
 };
 
@@ -22,23 +25,30 @@ use crate::{
 pub fn update_on_enumeration_change(
     model: &mut Model,
     path: &PathBuf,
-    contents: &[(ContentKind, String)],
+    contents: &[DirectoryContent],
     selection: &Option<String>,
 ) -> Vec<Action> {
     // TODO: handle unsaved changes
     let directories = model.files.get_mut_directories();
+    let directory_paths: Vec<_> = directories
+        .iter()
+        .map(|(p, _, _, _)| p.to_path_buf())
+        .collect();
     if let Some((path, viewport, cursor, buffer)) =
         directories.into_iter().find(|(p, _, _, _)| p == path)
     {
         tracing::trace!("enumeration changed for buffer: {:?}", path);
 
         let is_first_changed_event = buffer.lines.is_empty();
+        let patterns = get_ignore_patterns(&model.settings, path);
         let content = contents
             .iter()
-            .map(|(knd, cntnt)| {
-                let mut line = from_enumeration(cntnt, knd);
-                set_sign_if_marked(&model.marks, &mut line, &path.join(cntnt));
-                set_sign_if_qfix(&model.qfix, &mut line, &path.join(cntnt));
+            .filter(|cntnt| is_visible(&model.settings, &patterns, path, cntnt))
+            .map(|cntnt| {
+                let mut line = from_enumeration(&cntnt.name, &cntnt.kind);
+                set_columns(&model.settings, &mut model.owners, &mut line, cntnt);
+                set_sign_if_marked(&model.marks, &mut line, &path.join(&cntnt.name));
+                set_sign_if_qfix(&model.qfix, &mut line, &path.join(&cntnt.name));
 
                 line
             })
@@ -61,6 +71,17 @@ pub fn update_on_enumeration_change(
         }
     }
 
+    model
+        .files
+        .gitignores
+        .retain(|p, _| directory_paths.contains(p));
+
+    let enumerations = &mut model.files.enumerations;
+    enumerations.retain(|p, _| directory_paths.contains(p));
+    if directory_paths.contains(path) {
+        enumerations.insert(path.to_path_buf(), contents.to_vec());
+    }
+
     if path == &model.files.current.path {
         model.files.current.state = DirectoryBufferState::PartiallyLoaded;
     }
@@ -78,7 +99,7 @@ pub fn update_on_enumeration_change(
 pub fn update_on_enumeration_finished(
     model: &mut Model,
     path: &PathBuf,
-    contents: &[(ContentKind, String)],
+    contents: &[DirectoryContent],
     selection: &Option<String>,
 ) -> Vec<Action> {
     update_on_enumeration_change(model, path, contents, selection);
@@ -87,16 +108,20 @@ pub fn update_on_enumeration_finished(
         return Vec::new();
     }
 
+    let sort = get_sort_mode(&model.files.sorts, &model.settings, path).clone();
+    let contents = model.files.enumerations.get(path).cloned();
+
     let directories = model.files.get_mut_directories();
     if let Some((_, viewport, cursor, buffer)) =
         directories.into_iter().find(|(p, _, _, _)| p == path)
     {
-        update_buffer(
+        sort_buffer(
             viewport,
             cursor,
             &model.mode,
             buffer,
-            &BufferMessage::SortContent(super::SORT),
+            &sort,
+            &contents.unwrap_or_default(),
         );
 
         if let Some(selection) = selection {
@@ -168,11 +193,69 @@ pub fn update_on_enumeration_finished(
     actions
 }
 
+pub fn update_on_filter_change(model: &mut Model) -> Vec<Action> {
+    // NOTE: refreshing the current buffer while editing would drop unsaved changes, thus the
+    // refresh is deferred until navigation mode is entered again
+    let is_editing = is_editing(model);
+    model.files.current.is_filter_pending = is_editing;
+
+    let selections: Vec<_> = model
+        .files
+        .get_mut_directories()
+        .into_iter()
+        .map(|(path, _, cursor, buffer)| {
+            let selection = cursor
+                .as_ref()
+                .and_then(|crsr| buffer.lines.get(crsr.vertical_index))
+                .map(|line| line.content.to_stripped_string());
+
+            (path.to_path_buf(), selection)
+        })
+        .collect();
+
+    let mut actions = Vec::new();
+    for (path, selection) in selections {
+        if is_editing && path == model.files.current.path {
+            continue;
+        }
+
+        let contents = match model.files.enumerations.get(&path) {
+            Some(it) => it.clone(),
+            None => continue,
+        };
+
+        actions.extend(update_on_enumeration_finished(
+            model, &path, &contents, &selection,
+        ));
+
+        // NOTE: finished enumerations are only sorted in navigation mode
+        if model.mode != Mode::Navigation {
+            sort_directory(model, &path, selection);
+        }
+    }
+
+    actions
+}
+
+fn is_editing(model: &Model) -> bool {
+    let mode = if model.mode.is_command() {
+        model.mode_before.as_ref()
+    } else {
+        Some(&model.mode)
+    };
+
+    matches!(mode, Some(Mode::Insert | Mode::Normal))
+}
+
 // TODO: move to ansi before
 pub fn from_enumeration(content: &String, kind: &ContentKind) -> BufferLine {
     let content = match kind {
         ContentKind::Directory => format!("\x1b[94m{}\x1b[39m", content),
-        _ => content.to_string(),
+        ContentKind::File => content.to_string(),
+        ContentKind::Symlink {
+            is_broken: true, ..
+        } => format!("\x1b[91m{}\x1b[39m", content),
+        ContentKind::Symlink { .. } => format!("\x1b[96m{}\x1b[39m", content),
     };
 
     BufferLine {
@@ -180,3 +263,79 @@ pub fn from_enumeration(content: &String, kind: &ContentKind) -> BufferLine {
         ..Default::default()
     }
 }
+
+mod test {
+    #[test]
+    fn update_on_filter_change_defers_current_while_editing() {
+        use std::path::PathBuf;
+
+        use ratatui::layout::Rect;
+        use yeet_buffer::model::{ansi::Ansi, BufferLine, CommandMode, Mode};
+
+        use crate::{
+            event::{ContentKind, DirectoryContent},
+            model::Model,
+            update::mode::change_mode,
+        };
+
+        let content = |name: &str| DirectoryContent {
+            is_gitignored: false,
+            kind: ContentKind::File,
+            modified: None,
+            name: name.to_owned(),
+            permissions: None,
+            size: 0,
+            uid: None,
+        };
+
+        let path = PathBuf::from("/dir");
+        let mut model = Model {
+            mode: Mode::Command(CommandMode::Command),
+            mode_before: Some(Mode::Normal),
+            ..Default::default()
+        };
+        model.commandline.layout.buffer = Rect::new(0, 0, 80, 1);
+        model.layout.current = Rect::new(0, 0, 80, 10);
+        model.files.current.path = path.clone();
+        model
+            .files
+            .enumerations
+            .insert(path.clone(), vec![content("b"), content("a")]);
+        model.files.current.buffer.lines = vec![BufferLine {
+            content: Ansi::new("edited"),
+            ..Default::default()
+        }];
+
+        super::update_on_filter_change(&mut model);
+
+        assert!(model.files.current.is_filter_pending);
+        assert_eq!(
+            vec!["edited"],
+            model
+                .files
+                .current
+                .buffer
+                .lines
+                .iter()
+                .map(|line| line.content.to_stripped_string())
+                .collect::<Vec<_>>()
+        );
+
+        model.mode = Mode::Normal;
+        model.files.current.buffer.lines.clear();
+        change_mode(&mut model, &Mode::Normal, &Mode::Navigation);
+
+        assert!(!model.files.current.is_filter_pending);
+        assert_eq!(
+            vec!["a", "b"],
+            model
+                .files
+                .current
+                .buffer
+                .lines
+                .iter()
+                .map(|line| line.content.to_stripped_string())
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use std::path::Path;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::{
+    event::{ContentKind, DirectoryContent},
+    settings::Settings,
+};
+
+pub fn get_ignore_patterns(settings: &Settings, path: &Path) -> Option<Gitignore> {
+    if settings.ignore_patterns.is_empty() {
+        return None;
+    }
+
+    let mut builder = GitignoreBuilder::new(path);
+    for pattern in settings.ignore_patterns.iter() {
+        if let Err(error) = builder.add_line(None, pattern) {
+            tracing::warn!("invalid ignore pattern {:?}: {:?}", pattern, error);
+        }
+    }
+
+    match builder.build() {
+        Ok(it) => Some(it),
+        Err(error) => {
+            tracing::warn!("building ignore patterns failed: {:?}", error);
+            None
+        }
+    }
+}
+
+pub fn is_visible(
+    settings: &Settings,
+    patterns: &Option<Gitignore>,
+    path: &Path,
+    content: &DirectoryContent,
+) -> bool {
+    if !settings.show_hidden && content.name.starts_with('.') {
+        return false;
+    }
+
+    if settings.use_gitignore && content.is_gitignored {
+        return false;
+    }
+
+    match patterns {
+        Some(patterns) => !patterns
+            .matched(
+                path.join(&content.name),
+                content.kind == ContentKind::Directory,
+            )
+            .is_ignore(),
+        None => true,
+    }
+}
+
+mod test {
+    #[test]
+    fn is_visible() {
+        use std::path::Path;
+
+        use crate::{
+            event::{ContentKind, DirectoryContent},
+            settings::Settings,
+        };
+
+        let content = |name: &str, kind: ContentKind, is_gitignored: bool| DirectoryContent {
+            is_gitignored,
+            kind,
+            modified: None,
+            name: name.to_owned(),
+            permissions: None,
+            size: 0,
+            uid: None,
+        };
+
+        let mut settings = Settings {
+            ignore_patterns: vec!["*.o".to_owned(), "build/".to_owned()],
+            ..Default::default()
+        };
+
+        let path = Path::new("/home/user/project");
+        let patterns = super::get_ignore_patterns(&settings, path);
+
+        let visible = |settings: &Settings, content: &DirectoryContent| {
+            super::is_visible(settings, &patterns, path, content)
+        };
+
+        assert!(visible(
+            &settings,
+            &content("src", ContentKind::Directory, false)
+        ));
+        assert!(!visible(
+            &settings,
+            &content(".git", ContentKind::Directory, false)
+        ));
+        assert!(!visible(
+            &settings,
+            &content("target", ContentKind::Directory, true)
+        ));
+        assert!(!visible(
+            &settings,
+            &content("main.o", ContentKind::File, false)
+        ));
+        assert!(!visible(
+            &settings,
+            &content("build", ContentKind::Directory, false)
+        ));
+        assert!(visible(
+            &settings,
+            &content("build", ContentKind::File, false)
+        ));
+
+        settings.show_hidden = true;
+        assert!(visible(
+            &settings,
+            &content(".git", ContentKind::Directory, false)
+        ));
+        assert!(!visible(
+            &settings,
+            &content("target", ContentKind::Directory, true)
+        ));
+        assert!(!visible(
+            &settings,
+            &content("main.o", ContentKind::File, false)
+        ));
+
+        settings.use_gitignore = false;
+        settings.ignore_patterns.clear();
+        let patterns = super::get_ignore_patterns(&settings, path);
+        assert!(super::is_visible(
+            &settings,
+            &patterns,
+            path,
+            &content("target", ContentKind::Directory, true)
+        ));
+        assert!(super::is_visible(
+            &settings,
+            &patterns,
+            path,
+            &content("main.o", ContentKind::File, false)
+        ));
+    }
+}
@@ -0,0 +1,362 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use yeet_buffer::{
+    message::{BufferMessage, TextModification},
+    model::{
+        ansi::Ansi, viewport::LineNumber, viewport::ViewPort, BufferLine, CommandMode, Cursor, Mode,
+    },
+    update::update_buffer,
+};
+use yeet_keymap::message::KeymapMessage;
+
+use crate::{
+    action::Action,
+    event::to_human_readable_size,
+    model::{
+        junkyard::{FileEntry, FileEntryStatus, JunkYard, JunkYardBrowser},
+        Model, WindowType,
+    },
+    task::Task,
+};
+
+use super::{
+    column::format_time, history, selection, update_with_keymap_message,
+    viewport::set_viewport_dimensions,
+};
+
+pub fn open(model: &mut Model) -> Vec<Action> {
+    model.qfix_editor = None;
+    model.junk_browser = Some(JunkYardBrowser {
+        cursor: Some(Cursor::default()),
+        viewport: ViewPort {
+            line_number: LineNumber::Relative,
+            line_number_width: 3,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    refresh(model);
+    load_preview(model)
+}
+
+pub fn close(model: &mut Model) -> Vec<Action> {
+    model.junk_browser = None;
+
+    let mut actions = Vec::new();
+    if let Some(path) = selection::get_current_selected_path(model) {
+        let selection =
+            history::get_selection_from_history(&model.history, &path).map(|s| s.to_owned());
+        actions.push(Action::Load(WindowType::Preview, path, selection));
+    }
+
+    actions
+}
+
+pub fn refresh(model: &mut Model) {
+    let browser = match model.junk_browser.as_mut() {
+        Some(it) => it,
+        None => return,
+    };
+
+    let (entries, lines) = get_lines(&model.junk);
+    browser.entries = entries;
+
+    set_viewport_dimensions(&mut browser.viewport, &model.layout.current);
+    update_buffer(
+        &mut browser.viewport,
+        &mut browser.cursor,
+        &model.mode,
+        &mut browser.buffer,
+        &BufferMessage::SetContent(lines),
+    );
+}
+
+pub fn update(model: &mut Model, msg: &KeymapMessage) -> Option<Vec<Action>> {
+    // NOTE: commands target the directory, thus the browser gets closed to reveal it first
+    let is_command = matches!(
+        msg,
+        KeymapMessage::ExecuteCommand | KeymapMessage::ExecuteCommandString(_)
+    );
+    if model.junk_browser.is_none() || (model.mode != Mode::Navigation && !is_command) {
+        return None;
+    }
+
+    let actions = match msg {
+        KeymapMessage::ExecuteCommand | KeymapMessage::ExecuteCommandString(_) => {
+            let mut actions = close(model);
+            actions.extend(update_with_keymap_message(model, msg));
+            actions
+        }
+        KeymapMessage::Buffer(BufferMessage::ChangeMode(
+            _,
+            Mode::Command(CommandMode::Search(_)),
+        )) => Vec::new(),
+        KeymapMessage::Buffer(BufferMessage::ChangeMode(_, Mode::Command(_))) => return None,
+        KeymapMessage::Buffer(BufferMessage::ChangeMode(_, _)) => Vec::new(),
+        KeymapMessage::Buffer(BufferMessage::Modification(_, TextModification::DeleteLine)) => {
+            purge(model)
+        }
+        KeymapMessage::Buffer(
+            msg @ (BufferMessage::MoveCursor(_, _) | BufferMessage::MoveViewPort(_)),
+        ) => move_cursor(model, msg),
+        KeymapMessage::NavigateToMark(_)
+        | KeymapMessage::NavigateToPath(_)
+        | KeymapMessage::NavigateToPathAsPreview(_) => {
+            model.junk_browser = None;
+            return None;
+        }
+        KeymapMessage::NavigateToParent => close(model),
+        KeymapMessage::NavigateToSelected | KeymapMessage::OpenSelected => {
+            match get_selected_entry(model) {
+                Some(entry) => match entry.target.parent() {
+                    Some(parent) => restore(model, &entry, parent.to_path_buf()),
+                    None => Vec::new(),
+                },
+                None => Vec::new(),
+            }
+        }
+        KeymapMessage::PasteFromJunkYard(_) => match get_selected_entry(model) {
+            Some(entry) => restore(model, &entry, model.files.current.path.clone()),
+            None => Vec::new(),
+        },
+        KeymapMessage::SetMark(_)
+        | KeymapMessage::ToggleQuickFix
+        | KeymapMessage::YankPathToClipboard
+        | KeymapMessage::YankToJunkYard(_) => Vec::new(),
+        _ => return None,
+    };
+
+    Some(actions)
+}
+
+fn move_cursor(model: &mut Model, msg: &BufferMessage) -> Vec<Action> {
+    let selected = get_selected_entry(model);
+    if let Some(browser) = model.junk_browser.as_mut() {
+        set_viewport_dimensions(&mut browser.viewport, &model.layout.current);
+        update_buffer(
+            &mut browser.viewport,
+            &mut browser.cursor,
+            &model.mode,
+            &mut browser.buffer,
+            msg,
+        );
+    }
+
+    if selected == get_selected_entry(model) {
+        Vec::new()
+    } else {
+        load_preview(model)
+    }
+}
+
+fn purge(model: &mut Model) -> Vec<Action> {
+    match get_selected_entry(model) {
+        Some(entry) => vec![Action::Task(Task::DeleteJunkYardEntry(entry))],
+        None => Vec::new(),
+    }
+}
+
+fn restore(model: &Model, entry: &FileEntry, target: PathBuf) -> Vec<Action> {
+    vec![Action::Task(Task::RestorePath(
+        entry.clone(),
+        target,
+        model.settings.conflict_policy.clone(),
+    ))]
+}
+
+fn load_preview(model: &Model) -> Vec<Action> {
+    match get_selected_entry(model) {
+        Some(entry) if entry.status == FileEntryStatus::Ready => {
+            vec![Action::Task(Task::LoadArchivePreview(entry))]
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn get_selected_entry(model: &Model) -> Option<FileEntry> {
+    let browser = model.junk_browser.as_ref()?;
+    let cursor = browser.cursor.as_ref()?;
+
+    browser.entries.get(cursor.vertical_index).cloned()
+}
+
+fn get_lines(junk: &JunkYard) -> (Vec<FileEntry>, Vec<BufferLine>) {
+    // NOTE: numbers match :junk and :junkrestore, which accepts numbers beyond the registers
+    let yanked = junk
+        .yanked
+        .iter()
+        .map(|transaction| ("0".to_owned(), transaction));
+    let trashed = junk
+        .trashed
+        .iter()
+        .enumerate()
+        .map(|(index, transaction)| ((index + 1).to_string(), transaction));
+
+    let mut entries = Vec::new();
+    let mut lines = Vec::new();
+    for (register, transaction) in yanked.chain(trashed) {
+        let added_at = match transaction.id.parse::<u64>() {
+            Ok(millis) => format_time(UNIX_EPOCH + Duration::from_millis(millis)),
+            Err(_) => "-".to_owned(),
+        };
+
+        for entry in transaction.entries.iter() {
+            let size = match (&entry.status, entry.size) {
+                (FileEntryStatus::Ready, Some(size)) => to_human_readable_size(size),
+                _ => "-".to_owned(),
+            };
+
+            let content = format!(
+                "{:<2} {:<16} {:>5} {}",
+                register,
+                added_at,
+                size,
+                entry.target.display()
+            );
+
+            entries.push(entry.clone());
+            lines.push(BufferLine {
+                content: Ansi::new(&content),
+                ..Default::default()
+            });
+        }
+    }
+
+    (entries, lines)
+}
+
+mod test {
+    #[test]
+    fn update_restore_paste_and_close() {
+        use std::path::{Path, PathBuf};
+
+        use ratatui::layout::Rect;
+
+        use yeet_buffer::{
+            message::{BufferMessage, CursorDirection},
+            model::Mode,
+        };
+        use yeet_keymap::message::KeymapMessage;
+
+        use crate::{
+            action::Action,
+            model::{junkyard::FileEntryStatus, Model},
+            task::Task,
+            update::junkyard::trash_to_junkyard,
+        };
+
+        let mut model = Model {
+            mode: Mode::Navigation,
+            ..Default::default()
+        };
+        model.files.current.path = PathBuf::from("/current");
+        model.layout.current = Rect::new(0, 0, 80, 20);
+
+        let paths = vec![PathBuf::from("/origin/a"), PathBuf::from("/origin/b")];
+        trash_to_junkyard(&mut model.junk, paths, &Default::default());
+        for entry in model.junk.trashed[0].entries.iter_mut() {
+            entry.status = FileEntryStatus::Ready;
+        }
+
+        assert!(super::update(&mut model, &KeymapMessage::NavigateToParent).is_none());
+
+        super::open(&mut model);
+        let browser = model.junk_browser.as_ref().expect("browser is open");
+        assert_eq!(2, browser.entries.len());
+        assert!(browser.buffer.lines[0]
+            .content
+            .to_stripped_string()
+            .starts_with("1 "));
+
+        let msg = KeymapMessage::Buffer(BufferMessage::MoveCursor(1, CursorDirection::Down));
+        let actions = super::update(&mut model, &msg).expect("handled by browser");
+        assert!(matches!(
+            actions.first(),
+            Some(Action::Task(Task::LoadArchivePreview(entry)))
+                if entry.target == Path::new("/origin/b")
+        ));
+
+        let actions = super::update(&mut model, &KeymapMessage::OpenSelected);
+        assert!(matches!(
+            actions.as_deref(),
+            Some([Action::Task(Task::RestorePath(entry, path, _))])
+                if entry.target == Path::new("/origin/b") && path == Path::new("/origin")
+        ));
+
+        let actions = super::update(&mut model, &KeymapMessage::PasteFromJunkYard('"'));
+        assert!(matches!(
+            actions.as_deref(),
+            Some([Action::Task(Task::RestorePath(_, path, _))])
+                if path == Path::new("/current")
+        ));
+
+        super::update(&mut model, &KeymapMessage::NavigateToParent);
+        assert!(model.junk_browser.is_none());
+    }
+
+    #[test]
+    fn update_numbers_entries_and_closes_for_commands() {
+        use std::path::PathBuf;
+
+        use ratatui::layout::Rect;
+
+        use yeet_buffer::{
+            message::BufferMessage,
+            model::{CommandMode, Mode, SearchDirection},
+        };
+        use yeet_keymap::message::KeymapMessage;
+
+        use crate::{
+            model::{junkyard::FileEntryStatus, Model},
+            update::junkyard::trash_to_junkyard,
+        };
+
+        let mut model = Model {
+            mode: Mode::Navigation,
+            ..Default::default()
+        };
+        model.layout.current = Rect::new(0, 0, 80, 20);
+
+        for index in 0..10 {
+            let path = PathBuf::from(format!("/origin/{}", index));
+            trash_to_junkyard(&mut model.junk, vec![path], &Default::default());
+        }
+
+        let entry = &mut model.junk.trashed[0].entries[0];
+        entry.size = Some(2048);
+        entry.status = FileEntryStatus::Ready;
+
+        super::open(&mut model);
+        let browser = model.junk_browser.as_ref().expect("browser is open");
+        assert!(browser.buffer.lines[0]
+            .content
+            .to_stripped_string()
+            .contains(" 2.0K /origin/9"));
+        assert!(browser.buffer.lines[1]
+            .content
+            .to_stripped_string()
+            .contains(" - /origin/8"));
+        assert!(browser.buffer.lines[8]
+            .content
+            .to_stripped_string()
+            .starts_with("9  "));
+        assert!(browser.buffer.lines[9]
+            .content
+            .to_stripped_string()
+            .starts_with("10 "));
+
+        let msg = KeymapMessage::Buffer(BufferMessage::ChangeMode(
+            Mode::Navigation,
+            Mode::Command(CommandMode::Search(SearchDirection::Down)),
+        ));
+        assert_eq!(Some(0), super::update(&mut model, &msg).map(|it| it.len()));
+
+        let msg = KeymapMessage::ExecuteCommandString("junkclean".to_owned());
+        super::update(&mut model, &msg).expect("handled by browser");
+        assert!(model.junk_browser.is_none());
+    }
+}
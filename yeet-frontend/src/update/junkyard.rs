@@ -4,72 +4,192 @@ use std::{
     time,
 };
 
+use yeet_buffer::{message::BufferMessage, model::Mode};
+use yeet_keymap::message::{KeymapMessage, PrintContent};
+
 use crate::{
-    action::Action,
+    action::{self, Action},
     model::{
-        junkyard::{FileEntry, FileEntryStatus, FileEntryType, FileTransaction, JunkYard},
+        junkyard::{
+            FileEntry, FileEntryFormat, FileEntryStatus, FileEntryType, FileTransaction, JunkYard,
+            TrashBackend,
+        },
         Model,
     },
+    settings::JunkYardSettings,
     task::Task,
 };
 
+use super::{junkbrowser, selection::get_current_selected_range};
+
 pub fn add_to_junkyard(model: &mut Model, paths: &Vec<PathBuf>) -> Vec<Action> {
     let mut actions = Vec::new();
     for path in paths {
-        if path.starts_with(&model.junk.path) {
-            if let Some(obsolete) = add_or_update_junkyard_entry(&mut model.junk, path) {
-                for entry in obsolete.entries {
-                    actions.push(Action::Task(Task::DeleteJunkYardEntry(entry)));
-                }
-            }
+        if is_junkyard_path(&model.junk, path) {
+            add_or_update_junkyard_entry(&mut model.junk, path);
+            actions.push(Action::Task(Task::MeasureJunkYardEntry(path.clone())));
         }
     }
 
+    if !actions.is_empty() {
+        let obsolete = evict_from_junkyard(&mut model.junk, &model.settings.junkyard);
+        junkbrowser::refresh(model);
+
+        actions.extend(get_delete_actions(obsolete));
+    }
+
+    actions
+}
+
+pub fn set_junkyard_entry_size(model: &mut Model, path: &Path, size: u64) -> Vec<Action> {
+    let junk = &mut model.junk;
+    let entries = junk
+        .yanked
+        .iter_mut()
+        .chain(junk.trashed.iter_mut())
+        .flat_map(|transaction| transaction.entries.iter_mut())
+        .filter(|entry| entry.cache == path);
+
+    let mut changed = false;
+    for entry in entries {
+        entry.size = Some(size);
+        changed = true;
+    }
+
+    if !changed {
+        return Vec::new();
+    }
+
+    let obsolete = evict_from_junkyard(&mut model.junk, &model.settings.junkyard);
+    junkbrowser::refresh(model);
+
+    get_delete_actions(obsolete)
+}
+
+pub fn clean_junkyard(model: &mut Model) -> Vec<Action> {
+    let obsolete = evict_from_junkyard(&mut model.junk, &model.settings.junkyard);
+    let content = vec![PrintContent::Information(format!(
+        "removed {} junk yard entries",
+        obsolete.len()
+    ))];
+
+    let mut actions = get_delete_actions(obsolete);
+    actions.push(action::emit_keymap(KeymapMessage::Print(content)));
     actions
 }
 
-pub fn add_or_update_junkyard_entry(
+pub fn evict_from_junkyard(
     junkyard: &mut JunkYard,
-    path: &Path,
-) -> Option<FileTransaction> {
+    settings: &JunkYardSettings,
+) -> Vec<FileTransaction> {
+    let now = match time::SystemTime::now().duration_since(time::UNIX_EPOCH) {
+        Ok(time) => time.as_millis(),
+        Err(_) => 0,
+    };
+
+    let max_age = u128::from(settings.max_age_days) * 24 * 60 * 60 * 1000;
+    let max_size = settings.max_size_mb.saturating_mul(1024 * 1024);
+
+    let mut size = 0;
+    let mut obsolete = Vec::new();
+    let mut retained = Vec::new();
+    for (index, transaction) in junkyard.trashed.drain(..).enumerate() {
+        if settings.max_size_mb > 0 {
+            size += get_transaction_size(&transaction);
+        }
+
+        let added_at = transaction.id.parse::<u128>().unwrap_or(now);
+        let is_obsolete = (settings.max_count > 0 && index >= settings.max_count)
+            || (settings.max_age_days > 0 && now.saturating_sub(added_at) > max_age)
+            || (settings.max_size_mb > 0 && size > max_size);
+
+        // NOTE: the latest trash is kept because its compression could still be in progress
+        if index > 0 && is_obsolete {
+            obsolete.push(transaction);
+        } else {
+            retained.push(transaction);
+        }
+    }
+
+    junkyard.trashed = retained;
+
+    obsolete
+}
+
+// NOTE: entries not measured yet count as empty until their size is known
+fn get_transaction_size(transaction: &FileTransaction) -> u64 {
+    transaction
+        .entries
+        .iter()
+        .filter_map(|entry| entry.size)
+        .sum()
+}
+
+pub fn get_delete_actions(obsolete: Vec<FileTransaction>) -> Vec<Action> {
+    obsolete
+        .into_iter()
+        .flat_map(|transaction| transaction.entries)
+        .map(|entry| Action::Task(Task::DeleteJunkYardEntry(entry)))
+        .collect()
+}
+
+pub fn is_junkyard_path(junkyard: &JunkYard, path: &Path) -> bool {
+    if path.starts_with(&junkyard.path) {
+        return true;
+    }
+
+    // NOTE: the freedesktop trash is shared with other applications, thus only yeet names count
+    let is_trash = !junkyard.trash_path.as_os_str().is_empty()
+        && path.parent() == Some(junkyard.trash_path.as_path());
+
+    is_trash
+        && decompose_compression_path(path)
+            .is_some_and(|(id, _, target)| id.parse::<u128>().is_ok() && target.is_absolute())
+}
+
+pub fn add_or_update_junkyard_entry(junkyard: &mut JunkYard, path: &Path) {
+    let uncompressed_path = get_uncompressed_path(junkyard);
+    let (cache, backend, format) = if path.starts_with(&uncompressed_path) {
+        (
+            uncompressed_path,
+            TrashBackend::JunkYard,
+            FileEntryFormat::Uncompressed,
+        )
+    } else if path.starts_with(&junkyard.path) {
+        (
+            junkyard.path.clone(),
+            TrashBackend::JunkYard,
+            FileEntryFormat::Compressed,
+        )
+    } else {
+        (
+            junkyard.trash_path.clone(),
+            TrashBackend::Freedesktop,
+            FileEntryFormat::Uncompressed,
+        )
+    };
+
     if let Some((id, file, target)) = decompose_compression_path(path) {
-        if junkyard.yanked.as_ref().is_some_and(|entry| entry.id == id) {
-            if let Some(transaction) = junkyard.yanked.as_mut() {
-                let entry = transaction
-                    .entries
-                    .iter_mut()
-                    .find(|entry| entry.id == file);
-
-                if let Some(entry) = entry {
-                    entry.status = FileEntryStatus::Ready;
-                } else {
-                    let mut entry = generate_file_entry(id.to_string(), &target, &junkyard.path);
-                    entry.status = FileEntryStatus::Ready;
-                    transaction.entries.push(entry);
-                }
-            }
+        let mut entry = generate_file_entry(id.to_string(), &target, &cache, backend, format);
+        entry.status = FileEntryStatus::Ready;
 
-            None
-        } else if let Some(index) = junkyard.trashed.iter().position(|entry| entry.id == id) {
-            let transaction = &mut junkyard.trashed[index];
-            let entry = transaction
+        let transaction = if junkyard.yanked.as_ref().is_some_and(|entry| entry.id == id) {
+            junkyard.yanked.as_mut()
+        } else {
+            junkyard.trashed.iter_mut().find(|entry| entry.id == id)
+        };
+
+        // NOTE: replacing the entry keeps cache and format in sync after background compression
+        if let Some(transaction) = transaction {
+            match transaction
                 .entries
                 .iter_mut()
-                .find(|entry| entry.id == file);
-
-            if let Some(entry) = entry {
-                entry.status = FileEntryStatus::Ready;
-            } else {
-                let mut entry = generate_file_entry(id.to_string(), &target, &junkyard.path);
-                entry.status = FileEntryStatus::Ready;
-                transaction.entries.push(entry);
+                .find(|entry| entry.id == file)
+            {
+                Some(existing) => *existing = entry,
+                None => transaction.entries.push(entry),
             }
-
-            None
         } else {
-            let mut entry = generate_file_entry(id.to_string(), &target, &junkyard.path);
-            entry.status = FileEntryStatus::Ready;
-
             junkyard.trashed.push(FileTransaction {
                 id: id.to_owned(),
                 entries: vec![entry],
@@ -78,24 +198,25 @@ pub fn add_or_update_junkyard_entry(
             junkyard
                 .trashed
                 .sort_unstable_by_key(|entry| Reverse(entry.id.clone()));
-
-            if junkyard.trashed.len() > 9 {
-                junkyard.trashed.pop()
-            } else {
-                None
-            }
         }
-    } else {
-        None
     }
 }
 
-fn generate_file_entry(id: String, path: &Path, cache: &Path) -> FileEntry {
+fn generate_file_entry(
+    id: String,
+    path: &Path,
+    cache: &Path,
+    backend: TrashBackend,
+    format: FileEntryFormat,
+) -> FileEntry {
     let id = compose_compression_name(id, path);
 
     FileEntry {
+        backend,
         cache: cache.join(&id),
+        format,
         id,
+        size: None,
         status: FileEntryStatus::default(),
         target: path.to_path_buf(),
     }
@@ -133,6 +254,10 @@ fn decompose_compression_path(path: &Path) -> Option<(String, String, PathBuf)>
     }
 }
 
+pub fn get_uncompressed_path(junkyard: &JunkYard) -> PathBuf {
+    junkyard.path.join(".raw")
+}
+
 pub fn paste_to_junkyard(model: &mut Model, entry_id: &char) -> Vec<Action> {
     if let Some(transaction) = get_junkyard_transaction(&model.junk, entry_id) {
         let mut actions = Vec::new();
@@ -140,6 +265,7 @@ pub fn paste_to_junkyard(model: &mut Model, entry_id: &char) -> Vec<Action> {
             actions.push(Action::Task(Task::RestorePath(
                 entry.clone(),
                 model.files.current.path.clone(),
+                model.settings.conflict_policy.clone(),
             )));
         }
         actions
@@ -148,14 +274,47 @@ pub fn paste_to_junkyard(model: &mut Model, entry_id: &char) -> Vec<Action> {
     }
 }
 
+pub fn restore_from_junkyard(model: &mut Model, entry_id: &char) -> Vec<Action> {
+    let transaction = get_junkyard_transaction(&model.junk, entry_id);
+    restore_transaction(model, transaction)
+}
+
+pub fn restore_trash_from_junkyard(model: &mut Model, index: usize) -> Vec<Action> {
+    let transaction = model.junk.trashed.get(index).filter(|it| is_ready(it));
+    restore_transaction(model, transaction)
+}
+
+fn restore_transaction(model: &Model, transaction: Option<&FileTransaction>) -> Vec<Action> {
+    if let Some(transaction) = transaction {
+        let mut actions = Vec::new();
+        for entry in transaction.entries.iter() {
+            if let Some(parent) = entry.target.parent() {
+                actions.push(Action::Task(Task::RestorePath(
+                    entry.clone(),
+                    parent.to_path_buf(),
+                    model.settings.conflict_policy.clone(),
+                )));
+            }
+        }
+        actions
+    } else {
+        Vec::new()
+    }
+}
+
 pub fn yank_to_junkyard(model: &mut Model, repeat: &usize) -> Vec<Action> {
     let current_buffer = &model.files.current.buffer;
     if current_buffer.lines.is_empty() {
         Vec::new()
     } else if let Some(cursor) = &model.files.current_cursor {
+        let (start, count, selected) = match get_current_selected_range(model) {
+            Some((start, end)) => (start, end - start + 1, true),
+            None => (cursor.vertical_index, *repeat, false),
+        };
+
         let mut paths = Vec::new();
-        for rpt in 0..*repeat {
-            let line_index = cursor.vertical_index + rpt;
+        for rpt in 0..count {
+            let line_index = start + rpt;
             if let Some(line) = current_buffer.lines.get(line_index) {
                 let target = model
                     .files
@@ -179,6 +338,12 @@ pub fn yank_to_junkyard(model: &mut Model, repeat: &usize) -> Vec<Action> {
             }
         }
 
+        if selected {
+            actions.push(action::emit_keymap(KeymapMessage::Buffer(
+                BufferMessage::ChangeMode(model.mode.clone(), Mode::Navigation),
+            )));
+        }
+
         actions
     } else {
         Vec::new()
@@ -191,11 +356,21 @@ fn yank(
 ) -> (FileTransaction, Option<FileTransaction>) {
     junkyard.current = FileEntryType::Yank;
 
-    let transaction = generate_file_transaction(paths, junkyard);
+    let transaction = generate_file_transaction(
+        paths,
+        &junkyard.path,
+        TrashBackend::JunkYard,
+        FileEntryFormat::Compressed,
+    );
     (transaction.clone(), junkyard.yanked.replace(transaction))
 }
 
-fn generate_file_transaction(paths: Vec<PathBuf>, junk: &JunkYard) -> FileTransaction {
+fn generate_file_transaction(
+    paths: Vec<PathBuf>,
+    cache: &Path,
+    backend: TrashBackend,
+    format: FileEntryFormat,
+) -> FileTransaction {
     let added_at = match time::SystemTime::now().duration_since(time::UNIX_EPOCH) {
         Ok(time) => time.as_millis(),
         Err(_) => 0,
@@ -203,7 +378,15 @@ fn generate_file_transaction(paths: Vec<PathBuf>, junk: &JunkYard) -> FileTransa
 
     let entries = paths
         .into_iter()
-        .map(|path| generate_file_entry(added_at.to_string(), &path, &junk.path))
+        .map(|path| {
+            generate_file_entry(
+                added_at.to_string(),
+                &path,
+                cache,
+                backend.clone(),
+                format.clone(),
+            )
+        })
         .collect();
 
     FileTransaction {
@@ -236,24 +419,39 @@ pub fn get_junkyard_transaction<'a>(
         _ => None,
     };
 
-    let is_ready = transaction.as_ref().is_some_and(|trnsctn| {
-        trnsctn
+    transaction.filter(|it| is_ready(it))
+}
+
+fn is_ready(transaction: &FileTransaction) -> bool {
+    transaction
+        .entries
+        .iter()
+        .all(|entry| entry.status == FileEntryStatus::Ready)
+}
+
+pub fn remove_from_junkyard(junkyard: &mut JunkYard, path: &Path) {
+    // NOTE: matching the cache ignores the uncompressed copy removed after compression
+    let contains = |transaction: &FileTransaction| {
+        transaction
             .entries
             .iter()
-            .all(|entry| entry.status == FileEntryStatus::Ready)
-    });
+            .any(|entry| entry.cache.as_path() == path)
+    };
 
-    if is_ready {
-        transaction
-    } else {
-        None
+    if let Some(yanked) = junkyard.yanked.as_mut().filter(|entry| contains(entry)) {
+        yanked.entries.retain(|entry| entry.cache.as_path() != path);
+        if yanked.entries.is_empty() {
+            junkyard.yanked = None;
+        }
     }
-}
 
-pub fn remove_from_junkyard(junkyard: &mut JunkYard, path: &Path) {
-    if let Some((id, _, _)) = decompose_compression_path(path) {
-        let index = junkyard.trashed.iter().position(|entry| entry.id == id);
-        if let Some(index) = index {
+    let index = junkyard.trashed.iter().position(contains);
+    if let Some(index) = index {
+        let transaction = &mut junkyard.trashed[index];
+        transaction
+            .entries
+            .retain(|entry| entry.cache.as_path() != path);
+        if transaction.entries.is_empty() {
             junkyard.trashed.remove(index);
         }
     }
@@ -262,19 +460,24 @@ pub fn remove_from_junkyard(junkyard: &mut JunkYard, path: &Path) {
 pub fn trash_to_junkyard(
     junkyard: &mut JunkYard,
     paths: Vec<PathBuf>,
-) -> (FileTransaction, Option<FileTransaction>) {
+    backend: &TrashBackend,
+) -> FileTransaction {
     junkyard.current = FileEntryType::Trash;
 
-    let transaction = generate_file_transaction(paths, junkyard);
-    junkyard.trashed.insert(0, transaction.clone());
-
-    let obsolete = if junkyard.trashed.len() > 9 {
-        junkyard.trashed.pop()
-    } else {
-        None
+    let cache = match backend {
+        TrashBackend::Freedesktop => junkyard.trash_path.clone(),
+        TrashBackend::JunkYard => get_uncompressed_path(junkyard),
     };
 
-    (transaction, obsolete)
+    let transaction = generate_file_transaction(
+        paths,
+        &cache,
+        backend.clone(),
+        FileEntryFormat::Uncompressed,
+    );
+    junkyard.trashed.insert(0, transaction.clone());
+
+    transaction
 }
 
 mod test {
@@ -287,12 +490,13 @@ mod test {
         let mut junk = super::JunkYard {
             current: Default::default(),
             path: std::path::PathBuf::from("/some/path"),
+            trash_path: std::path::PathBuf::new(),
             trashed: Vec::new(),
             yanked: None,
         };
 
         let path = PathBuf::from("/other/path/.direnv");
-        let (transaction, _) = trash_to_junkyard(&mut junk, vec![path]);
+        let transaction = trash_to_junkyard(&mut junk, vec![path], &Default::default());
 
         assert_eq!(1, junk.trashed.len());
         assert_eq!(
@@ -343,6 +547,255 @@ mod test {
         assert_eq!(transaction, junk.trashed[2].id);
     }
 
+    #[test]
+    fn evict_from_junkyard_by_count_and_age() {
+        use std::time;
+
+        use crate::{
+            model::junkyard::FileTransaction, settings::JunkYardSettings,
+            update::junkyard::evict_from_junkyard,
+        };
+
+        let now = time::SystemTime::now()
+            .duration_since(time::UNIX_EPOCH)
+            .expect("time after epoch")
+            .as_millis();
+
+        let day = 24 * 60 * 60 * 1000;
+        let mut junk = super::JunkYard {
+            current: Default::default(),
+            path: std::path::PathBuf::from("/some/path"),
+            trash_path: std::path::PathBuf::new(),
+            trashed: [0, 1, 2, 10, 40]
+                .iter()
+                .map(|age| FileTransaction {
+                    id: (now - age * day).to_string(),
+                    entries: Vec::new(),
+                })
+                .collect(),
+            yanked: None,
+        };
+
+        let mut settings = JunkYardSettings {
+            compress: false,
+            max_age_days: 0,
+            max_count: 4,
+            max_size_mb: 0,
+        };
+
+        let obsolete = evict_from_junkyard(&mut junk, &settings);
+        assert_eq!(1, obsolete.len());
+        assert_eq!(4, junk.trashed.len());
+
+        settings.max_age_days = 5;
+        let obsolete = evict_from_junkyard(&mut junk, &settings);
+        assert_eq!(1, obsolete.len());
+        assert_eq!(3, junk.trashed.len());
+
+        settings.max_count = 1;
+        settings.max_age_days = 0;
+        let obsolete = evict_from_junkyard(&mut junk, &settings);
+        assert_eq!(2, obsolete.len());
+        assert_eq!(now.to_string(), junk.trashed[0].id);
+    }
+
+    #[test]
+    fn evict_from_junkyard_by_measured_size() {
+        use std::path::PathBuf;
+
+        use crate::{
+            action::Action,
+            model::Model,
+            task::Task,
+            update::junkyard::{evict_from_junkyard, set_junkyard_entry_size, trash_to_junkyard},
+        };
+
+        let mut model = Model::default();
+        model.settings.junkyard.max_size_mb = 1;
+
+        let older = trash_to_junkyard(
+            &mut model.junk,
+            vec![PathBuf::from("/a")],
+            &Default::default(),
+        );
+        model.junk.trashed[0].id = "1".to_owned();
+        let newer = trash_to_junkyard(
+            &mut model.junk,
+            vec![PathBuf::from("/b")],
+            &Default::default(),
+        );
+
+        let actions = set_junkyard_entry_size(&mut model, &newer.entries[0].cache, 1024 * 1024);
+        assert!(actions.is_empty());
+        assert_eq!(Some(1024 * 1024), model.junk.trashed[0].entries[0].size);
+
+        let actions = set_junkyard_entry_size(&mut model, &older.entries[0].cache, 1);
+        assert_eq!(1, model.junk.trashed.len());
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::Task(Task::DeleteJunkYardEntry(entry))] if entry.cache == older.entries[0].cache
+        ));
+
+        model.settings.junkyard.max_size_mb = u64::MAX;
+        assert!(evict_from_junkyard(&mut model.junk, &model.settings.junkyard).is_empty());
+    }
+
+    #[test]
+    fn restore_from_junkyard_to_origin() {
+        use std::path::PathBuf;
+
+        use crate::{
+            action::Action,
+            model::Model,
+            task::Task,
+            update::junkyard::{restore_from_junkyard, trash_to_junkyard},
+        };
+
+        let mut model = Model::default();
+        let paths = vec![
+            PathBuf::from("/home/user/src/file"),
+            PathBuf::from("/home/user/removed/dir"),
+        ];
+
+        let mut transaction = trash_to_junkyard(&mut model.junk, paths, &Default::default());
+        assert!(restore_from_junkyard(&mut model, &'1').is_empty());
+
+        for entry in transaction.entries.iter_mut() {
+            entry.status = super::FileEntryStatus::Ready;
+        }
+        model.junk.trashed[0] = transaction.clone();
+
+        let actions = restore_from_junkyard(&mut model, &'1');
+        let targets: Vec<_> = actions
+            .iter()
+            .filter_map(|action| match action {
+                Action::Task(Task::RestorePath(entry, path, _)) => Some((entry, path)),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(2, targets.len());
+        assert_eq!(&transaction.entries[0], targets[0].0);
+        assert_eq!(&PathBuf::from("/home/user/src"), targets[0].1);
+        assert_eq!(&PathBuf::from("/home/user/removed"), targets[1].1);
+    }
+
+    #[test]
+    fn remove_from_junkyard_by_entry() {
+        use std::path::PathBuf;
+
+        use crate::update::junkyard::{remove_from_junkyard, trash_to_junkyard};
+
+        let mut junk = super::JunkYard {
+            path: PathBuf::from("/some/path"),
+            ..Default::default()
+        };
+
+        let paths = vec![PathBuf::from("/other/a"), PathBuf::from("/other/b")];
+        let transaction = trash_to_junkyard(&mut junk, paths, &Default::default());
+
+        remove_from_junkyard(&mut junk, &transaction.entries[0].cache);
+        assert_eq!(1, junk.trashed.len());
+        assert_eq!(transaction.entries[1], junk.trashed[0].entries[0]);
+
+        remove_from_junkyard(&mut junk, &transaction.entries[1].cache);
+        assert!(junk.trashed.is_empty());
+    }
+
+    #[test]
+    fn junkyard_entry_from_uncompressed_to_compressed() {
+        use std::path::PathBuf;
+
+        use crate::{
+            model::junkyard::FileEntryFormat,
+            update::junkyard::{
+                add_or_update_junkyard_entry, remove_from_junkyard, trash_to_junkyard,
+            },
+        };
+
+        let mut junk = super::JunkYard {
+            path: PathBuf::from("/cache/junkyard"),
+            ..Default::default()
+        };
+
+        let paths = vec![PathBuf::from("/home/user/src")];
+        let transaction = trash_to_junkyard(&mut junk, paths, &Default::default());
+        let entry = &transaction.entries[0];
+
+        assert_eq!(FileEntryFormat::Uncompressed, entry.format);
+        assert!(entry.cache.starts_with("/cache/junkyard/.raw"));
+
+        add_or_update_junkyard_entry(&mut junk, &entry.cache);
+        assert_eq!(
+            super::FileEntryStatus::Ready,
+            junk.trashed[0].entries[0].status
+        );
+        assert_eq!(
+            FileEntryFormat::Uncompressed,
+            junk.trashed[0].entries[0].format
+        );
+
+        let archive = PathBuf::from("/cache/junkyard").join(&entry.id);
+        add_or_update_junkyard_entry(&mut junk, &archive);
+        assert_eq!(1, junk.trashed[0].entries.len());
+        assert_eq!(
+            FileEntryFormat::Compressed,
+            junk.trashed[0].entries[0].format
+        );
+        assert_eq!(archive, junk.trashed[0].entries[0].cache);
+
+        remove_from_junkyard(&mut junk, &entry.cache);
+        assert_eq!(1, junk.trashed.len());
+
+        remove_from_junkyard(&mut junk, &archive);
+        assert!(junk.trashed.is_empty());
+    }
+
+    #[test]
+    fn junkyard_entry_from_freedesktop_trash() {
+        use std::path::PathBuf;
+
+        use crate::{
+            model::junkyard::TrashBackend,
+            update::junkyard::{add_or_update_junkyard_entry, is_junkyard_path, trash_to_junkyard},
+        };
+
+        let mut junk = super::JunkYard {
+            path: PathBuf::from("/cache/junkyard"),
+            trash_path: PathBuf::from("/data/Trash/files"),
+            ..Default::default()
+        };
+
+        let other = PathBuf::from("/data/Trash/files/100%done.txt");
+        assert!(!is_junkyard_path(&junk, &other));
+        assert!(!is_junkyard_path(
+            &junk,
+            &PathBuf::from("/data/Trash/files/notes.txt")
+        ));
+
+        let paths = vec![PathBuf::from("/home/user/notes.txt")];
+        let transaction = trash_to_junkyard(&mut junk, paths, &TrashBackend::Freedesktop);
+        let entry = &transaction.entries[0];
+
+        assert_eq!(TrashBackend::Freedesktop, entry.backend);
+        assert!(entry.cache.starts_with("/data/Trash/files"));
+        assert!(is_junkyard_path(&junk, &entry.cache));
+
+        add_or_update_junkyard_entry(&mut junk, &entry.cache);
+        assert_eq!(
+            super::FileEntryStatus::Ready,
+            junk.trashed[0].entries[0].status
+        );
+
+        junk.trashed.clear();
+        add_or_update_junkyard_entry(&mut junk, &entry.cache);
+        assert_eq!(
+            TrashBackend::Freedesktop,
+            junk.trashed[0].entries[0].backend
+        );
+        assert_eq!(entry.cache, junk.trashed[0].entries[0].cache);
+    }
+
     #[test]
     fn compose_decompose_compression_name_linux() {
         let id = "1708576379595".to_string();
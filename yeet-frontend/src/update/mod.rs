@@ -1,4 +1,4 @@
-use std::{cmp::Ordering, path::Path};
+use std::path::Path;
 
 use yeet_buffer::{
     message::BufferMessage,
@@ -10,7 +10,7 @@ use yeet_keymap::message::{KeySequence, KeymapMessage, PrintContent};
 use crate::{
     action::Action,
     event::{Envelope, Message, Preview},
-    model::{BufferType, Model, WindowType},
+    model::{qfix::QuickFixEntry, BufferType, Model, WindowType},
 };
 
 use self::{
@@ -20,10 +20,13 @@ use self::{
     },
     cursor::move_cursor,
     enumeration::{update_on_enumeration_change, update_on_enumeration_finished},
-    junkyard::{add_to_junkyard, paste_to_junkyard, yank_to_junkyard},
+    junkyard::{
+        add_to_junkyard, paste_to_junkyard, restore_from_junkyard, set_junkyard_entry_size,
+        yank_to_junkyard,
+    },
     mark::{add_mark, delete_mark},
     mode::{change_mode, set_mode_in_commandline, set_recording_in_commandline},
-    modification::modify_buffer,
+    modification::{modify_buffer, modify_selection, redo_modification, undo_modification},
     navigation::{
         navigate_to_mark, navigate_to_parent, navigate_to_path, navigate_to_path_as_preview,
         navigate_to_selected,
@@ -41,11 +44,16 @@ use self::{
     viewport::{move_viewport, set_viewport_dimensions},
 };
 
+pub mod bulkrename;
+mod column;
 mod command;
 pub mod commandline;
+mod conflict;
 mod cursor;
 mod enumeration;
+mod filter;
 pub mod history;
+mod junkbrowser;
 pub mod junkyard;
 mod mark;
 mod mode;
@@ -54,22 +62,18 @@ mod navigation;
 mod open;
 mod path;
 mod qfix;
+mod qfixeditor;
 mod register;
+mod remote;
 mod save;
 mod search;
 mod selection;
 mod settings;
 mod sign;
+mod sort;
 mod task;
 pub mod viewport;
 
-const SORT: fn(&BufferLine, &BufferLine) -> Ordering = |a, b| {
-    a.content
-        .to_stripped_string()
-        .to_ascii_uppercase()
-        .cmp(&b.content.to_stripped_string().to_ascii_uppercase())
-};
-
 #[tracing::instrument(skip(model))]
 pub fn update_model(model: &mut Model, envelope: Envelope) -> Vec<Action> {
     match &envelope.sequence {
@@ -100,16 +104,29 @@ pub fn update_model(model: &mut Model, envelope: Envelope) -> Vec<Action> {
 #[tracing::instrument(skip(model))]
 fn update_with_message(model: &mut Model, message: Message) -> Vec<Action> {
     match message {
+        Message::AddToQuickFix(paths) => {
+            let entries = paths.into_iter().map(QuickFixEntry::from).collect();
+            qfix::append(model, entries)
+        }
+        Message::BulkRenameEdited(base, paths, lines) => {
+            bulkrename::plan(model, &base, &paths, &lines)
+        }
+        Message::ConflictDetected(task) => conflict::add(model, task),
         Message::EnumerationChanged(path, contents, selection) => {
             update_on_enumeration_change(model, &path, &contents, &selection)
         }
-        Message::EnumerationFinished(path, contents, selection) => {
+        Message::EnumerationFinished(path, contents, selection, gitignore) => {
+            model.files.gitignores.insert(path.clone(), gitignore);
             update_on_enumeration_finished(model, &path, &contents, &selection)
         }
         Message::Error(error) => {
             print_in_commandline(model, &[PrintContent::Error(error.to_string())])
         }
-        Message::FdResult(paths) => qfix::add(model, paths),
+        Message::FdResult(title, paths) => {
+            let entries = paths.into_iter().map(QuickFixEntry::from).collect();
+            qfix::add(model, title, entries)
+        }
+        Message::JunkYardEntryMeasured(path, size) => set_junkyard_entry_size(model, &path, size),
         Message::Keymap(msg) => update_with_keymap_message(model, &msg),
         Message::PathRemoved(path) => remove_path(model, &path),
         Message::PathsAdded(paths) => add_paths(model, &paths)
@@ -117,18 +134,38 @@ fn update_with_message(model: &mut Model, message: Message) -> Vec<Action> {
             .chain(add_to_junkyard(model, &paths).into_iter())
             .collect(),
         Message::PreviewLoaded(content) => update_preview(model, content),
+        Message::QuickFixImported(title, entries) => qfix::add(model, title, entries),
         Message::Rerender => Vec::new(),
         Message::Resize(x, y) => vec![Action::Resize(x, y)],
+        Message::RgResult(title, entries) => qfix::add(model, title, entries),
+        Message::ShellFinished(content) => command::shell::finished(model, content),
+        Message::StateRequested(sender) => {
+            if sender.send(remote::get_state(model)).is_err() {
+                tracing::warn!("remote state request was dropped");
+            }
+            Vec::new()
+        }
         Message::TaskStarted(identifier, cancellation) => {
             task::add(model, identifier, cancellation)
         }
         Message::TaskEnded(identifier) => task::remove(model, identifier),
+        Message::TaskProgress(identifier, progress) => {
+            task::update_progress(model, identifier, progress)
+        }
         Message::ZoxideResult(path) => navigate_to_path(model, path.as_ref()),
     }
 }
 
 #[tracing::instrument(skip(model, msg))]
 pub fn update_with_keymap_message(model: &mut Model, msg: &KeymapMessage) -> Vec<Action> {
+    if let Some(actions) = junkbrowser::update(model, msg) {
+        return actions;
+    }
+
+    if let Some(actions) = qfixeditor::update(model, msg) {
+        return actions;
+    }
+
     match msg {
         KeymapMessage::Buffer(msg) => update_with_buffer_message(model, msg),
         KeymapMessage::ClearSearchHighlight => clear_search(model),
@@ -149,6 +186,7 @@ pub fn update_with_keymap_message(model: &mut Model, msg: &KeymapMessage) -> Vec
         KeymapMessage::PasteFromJunkYard(entry_id) => paste_to_junkyard(model, entry_id),
         KeymapMessage::Print(content) => print_in_commandline(model, content),
         KeymapMessage::ReplayMacro(char) => replay_macro_register(&mut model.register, char),
+        KeymapMessage::RestoreFromJunkYard(entry_id) => restore_from_junkyard(model, entry_id),
         KeymapMessage::SetMark(char) => add_mark(model, *char),
         KeymapMessage::StartMacro(identifier) => set_recording_in_commandline(model, *identifier),
         KeymapMessage::StopMacro => set_mode_in_commandline(model),
@@ -167,16 +205,29 @@ pub fn update_with_buffer_message(model: &mut Model, msg: &BufferMessage) -> Vec
             Mode::Command(_) => update_commandline_on_modification(model, repeat, modification),
             Mode::Insert | Mode::Normal => modify_buffer(model, repeat, modification),
             Mode::Navigation => Vec::new(),
+            Mode::VisualLine => modify_selection(model, modification),
         },
         BufferMessage::MoveCursor(rpt, mtn) => match &model.mode {
             Mode::Command(_) => update_commandline(model, Some(msg)),
-            Mode::Insert | Mode::Navigation | Mode::Normal => move_cursor(model, rpt, mtn),
+            Mode::Insert | Mode::Navigation | Mode::Normal | Mode::VisualLine => {
+                move_cursor(model, rpt, mtn)
+            }
         },
         BufferMessage::MoveViewPort(mtn) => match model.mode {
             Mode::Command(_) => update_commandline(model, Some(msg)),
-            Mode::Insert | Mode::Navigation | Mode::Normal => move_viewport(model, mtn),
+            Mode::Insert | Mode::Navigation | Mode::Normal | Mode::VisualLine => {
+                move_viewport(model, mtn)
+            }
+        },
+        BufferMessage::Redo => match model.mode {
+            Mode::Normal => redo_modification(model),
+            Mode::Command(_) | Mode::Insert | Mode::Navigation | Mode::VisualLine => Vec::new(),
         },
         BufferMessage::SaveBuffer => persist_path_changes(model),
+        BufferMessage::Undo => match model.mode {
+            Mode::Normal => undo_modification(model),
+            Mode::Command(_) | Mode::Insert | Mode::Navigation | Mode::VisualLine => Vec::new(),
+        },
 
         BufferMessage::RemoveLine(_)
         | BufferMessage::ResetCursor
@@ -216,6 +267,7 @@ pub fn update_preview(model: &mut Model, content: Preview) -> Vec<Action> {
                 .collect();
 
             buffer_type(&WindowType::Preview, model, &path, content);
+            qfix::highlight_location_in_preview(model, &path);
         }
         Preview::Image(path, protocol) => model.files.preview = BufferType::Image(path, protocol),
         Preview::None(_) => model.files.preview = BufferType::None,
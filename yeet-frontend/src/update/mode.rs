@@ -12,8 +12,8 @@ use crate::{
 };
 
 use super::{
-    commandline::print_in_commandline, register::get_macro_register, save::persist_path_changes,
-    viewport::set_viewport_dimensions,
+    commandline::print_in_commandline, enumeration::update_on_filter_change,
+    register::get_macro_register, save::persist_path_changes, viewport::set_viewport_dimensions,
 };
 
 pub fn change_mode(model: &mut Model, from: &Mode, to: &Mode) -> Vec<Action> {
@@ -21,7 +21,8 @@ pub fn change_mode(model: &mut Model, from: &Mode, to: &Mode) -> Vec<Action> {
         (Mode::Command(_), Mode::Command(_))
         | (Mode::Insert, Mode::Insert)
         | (Mode::Navigation, Mode::Navigation)
-        | (Mode::Normal, Mode::Normal) => return Vec::new(),
+        | (Mode::Normal, Mode::Normal)
+        | (Mode::VisualLine, Mode::VisualLine) => return Vec::new(),
         _ => {}
     }
 
@@ -34,7 +35,7 @@ pub fn change_mode(model: &mut Model, from: &Mode, to: &Mode) -> Vec<Action> {
             unfocus_buffer(&mut model.commandline.cursor);
             update_commandline_on_mode_change(model)
         }
-        Mode::Insert | Mode::Navigation | Mode::Normal => {
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::VisualLine => {
             unfocus_buffer(&mut model.files.current_cursor);
             vec![]
         }
@@ -58,9 +59,14 @@ pub fn change_mode(model: &mut Model, from: &Mode, to: &Mode) -> Vec<Action> {
             // TODO: sort and refresh current on PathEnumerationFinished while not in Navigation mode
             focus_buffer(&mut model.files.current_cursor);
             update_current(model, &msg);
-            persist_path_changes(model)
+
+            let mut actions = persist_path_changes(model);
+            if model.files.current.is_filter_pending {
+                actions.extend(update_on_filter_change(model));
+            }
+            actions
         }
-        Mode::Normal => {
+        Mode::Normal | Mode::VisualLine => {
             focus_buffer(&mut model.files.current_cursor);
             update_current(model, &msg);
             vec![]
@@ -79,7 +85,7 @@ fn update_commandline_on_mode_change(model: &mut Model) -> Vec<Action> {
 
     let command_mode = match &model.mode {
         Mode::Command(it) => it,
-        Mode::Insert | Mode::Navigation | Mode::Normal => {
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::VisualLine => {
             let from_command = model
                 .mode_before
                 .as_ref()
@@ -1,10 +1,23 @@
-use yeet_buffer::message::{BufferMessage, TextModification};
+use std::collections::HashMap;
+
+use yeet_buffer::{
+    message::{BufferMessage, TextModification},
+    model::Mode,
+};
+use yeet_keymap::message::KeymapMessage;
 
 use crate::{
     action::Action,
+    event::Message,
     model::{BufferType, Model},
 };
 
+use super::{
+    column::set_columns,
+    selection::get_current_selected_range,
+    sign::{set_sign_if_marked, set_sign_if_qfix},
+};
+
 pub fn modify_buffer(
     model: &mut Model,
     repeat: &usize,
@@ -17,3 +30,119 @@ pub fn modify_buffer(
 
     Vec::new()
 }
+
+pub fn modify_selection(model: &mut Model, modification: &TextModification) -> Vec<Action> {
+    let (start, end) = match get_current_selected_range(model) {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    if let Some(cursor) = model.files.current_cursor.as_mut() {
+        cursor.vertical_index = start;
+    }
+    super::update_current(model, &BufferMessage::UpdateViewPortByCursor);
+
+    vec![Action::EmitMessages(vec![
+        Message::Keymap(KeymapMessage::Buffer(BufferMessage::ChangeMode(
+            model.mode.clone(),
+            Mode::Normal,
+        ))),
+        Message::Keymap(KeymapMessage::Buffer(BufferMessage::Modification(
+            end - start + 1,
+            modification.clone(),
+        ))),
+    ])]
+}
+
+pub fn undo_modification(model: &mut Model) -> Vec<Action> {
+    super::update_current(model, &BufferMessage::Undo);
+    set_decorations(model);
+
+    model.files.preview = BufferType::None;
+
+    Vec::new()
+}
+
+pub fn redo_modification(model: &mut Model) -> Vec<Action> {
+    super::update_current(model, &BufferMessage::Redo);
+    set_decorations(model);
+
+    model.files.preview = BufferType::None;
+
+    Vec::new()
+}
+
+// NOTE: undo and redo restore line content only, thus columns and signs are set again
+fn set_decorations(model: &mut Model) {
+    let path = &model.files.current.path;
+    let contents: HashMap<_, _> = match model.files.enumerations.get(path) {
+        Some(it) => it
+            .iter()
+            .map(|content| (content.name.as_str(), content))
+            .collect(),
+        None => return,
+    };
+
+    for line in model.files.current.buffer.lines.iter_mut() {
+        let name = line.content.to_stripped_string();
+        let content = match contents.get(name.as_str()) {
+            Some(it) => it,
+            None => continue,
+        };
+
+        let path = path.join(&name);
+        set_columns(&model.settings, &mut model.owners, line, content);
+        set_sign_if_marked(&model.marks, line, &path);
+        set_sign_if_qfix(&model.qfix, line, &path);
+    }
+}
+
+mod test {
+    #[test]
+    fn undo_modification_restores_decorations() {
+        use std::path::PathBuf;
+
+        use ratatui::layout::Rect;
+        use yeet_buffer::{
+            message::TextModification,
+            model::{BufferLine, Mode},
+        };
+
+        use crate::{
+            event::{ContentKind, DirectoryContent},
+            model::Model,
+            update::{column::set_columns, sign::set_sign_if_marked},
+        };
+
+        let path = PathBuf::from("/dir");
+        let mut content = DirectoryContent::new("a".to_owned(), ContentKind::File, None);
+        content.size = 4300;
+
+        let mut model = Model {
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+        model.layout.current = Rect::new(0, 0, 80, 10);
+        model.settings.show_size = true;
+        model.files.current.path = path.clone();
+        model.marks.entries.insert('a', path.join("a"));
+        model
+            .files
+            .enumerations
+            .insert(path.clone(), vec![content.clone()]);
+
+        let mut line = BufferLine::from("a");
+        set_columns(&model.settings, &mut model.owners, &mut line, &content);
+        set_sign_if_marked(&model.marks, &mut line, &path.join("a"));
+        model.files.current.buffer.lines = vec![line.clone(), BufferLine::from("b")];
+
+        super::modify_buffer(&mut model, &1, &TextModification::DeleteLine);
+        assert_eq!(1, model.files.current.buffer.lines.len());
+
+        super::undo_modification(&mut model);
+        assert_eq!(Some(&line), model.files.current.buffer.lines.first());
+
+        super::redo_modification(&mut model);
+        assert_eq!(1, model.files.current.buffer.lines.len());
+    }
+}
@@ -1,7 +1,14 @@
+use std::path::Path;
+
 use yeet_buffer::model::Mode;
 use yeet_keymap::message::QuitMode;
 
-use crate::{action::Action, model::Model};
+use crate::{
+    action::Action,
+    event::Message,
+    model::{BufferType, Model},
+    settings::OpenerRule,
+};
 
 use super::selection::get_current_selected_path;
 
@@ -18,6 +25,10 @@ pub fn open_selected(model: &Model) -> Vec<Action> {
                 QuitMode::FailOnRunningTasks,
                 Some(selected.to_string_lossy().to_string()),
             )]
+        } else if let Some(rule) = get_opener_rule(model, &selected) {
+            vec![Action::OpenWith(selected, rule)]
+        } else if model.settings.open_text_in_editor && is_text_preview(model, &selected) {
+            vec![Action::OpenInForeground(selected)]
         } else {
             vec![Action::Open(selected)]
         }
@@ -25,3 +36,154 @@ pub fn open_selected(model: &Model) -> Vec<Action> {
         Vec::new()
     }
 }
+
+pub fn open_selected_with(model: &Model, command: &str) -> Vec<Action> {
+    match get_current_selected_path(model) {
+        Some(selected) => {
+            let rule = OpenerRule {
+                block: true,
+                command: command.to_owned(),
+                foreground: true,
+                ..Default::default()
+            };
+            vec![Action::OpenWith(selected, rule)]
+        }
+        None => {
+            let message = "openwith requires a selected path".to_owned();
+            vec![Action::EmitMessages(vec![Message::Error(message)])]
+        }
+    }
+}
+
+// NOTE: extensions are matched before mime types, the first matching rule wins
+fn get_opener_rule(model: &Model, path: &Path) -> Option<OpenerRule> {
+    let openers = &model.settings.openers;
+    if openers.is_empty() || !path.is_file() {
+        return None;
+    }
+
+    if let Some(extension) = path.extension().map(|ext| ext.to_string_lossy()) {
+        let rule = openers.iter().find(|rule| {
+            rule.extensions
+                .iter()
+                .any(|ext| ext.trim_start_matches('.').eq_ignore_ascii_case(&extension))
+        });
+
+        if rule.is_some() {
+            return rule.cloned();
+        }
+    }
+
+    let mime = match infer::get_from_path(path) {
+        Ok(Some(kind)) => kind.mime_type().to_owned(),
+        _ if is_text_preview(model, path) => "text/plain".to_owned(),
+        _ => return None,
+    };
+
+    openers
+        .iter()
+        .find(|rule| {
+            rule.mime
+                .as_deref()
+                .is_some_and(|pattern| is_mime_matching(pattern, &mime))
+        })
+        .cloned()
+}
+
+fn is_mime_matching(pattern: &str, mime: &str) -> bool {
+    match pattern.strip_suffix("/*") {
+        Some(kind) => mime
+            .split_once('/')
+            .is_some_and(|(mime_kind, _)| mime_kind.eq_ignore_ascii_case(kind)),
+        None => pattern == "*" || pattern.eq_ignore_ascii_case(mime),
+    }
+}
+
+fn is_text_preview(model: &Model, selected: &Path) -> bool {
+    match &model.files.preview {
+        BufferType::Text(path, _) => path == selected && path.is_file(),
+        BufferType::Image(_, _) | BufferType::None => false,
+    }
+}
+
+mod test {
+    #[test]
+    fn get_opener_rule() {
+        use std::fs;
+
+        use crate::{model::Model, settings::OpenerRule};
+
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let dir = directory.path();
+
+        let video = dir.join("clip.MKV");
+        fs::write(&video, b"").expect("write file");
+
+        let png = dir.join("image");
+        fs::write(&png, b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").expect("write file");
+
+        let mut model = Model::default();
+        model.settings.openers = vec![
+            OpenerRule {
+                command: "mpv".to_owned(),
+                extensions: vec!["mkv".to_owned()],
+                ..Default::default()
+            },
+            OpenerRule {
+                command: "imv".to_owned(),
+                mime: Some("image/*".to_owned()),
+                ..Default::default()
+            },
+        ];
+
+        let rule = super::get_opener_rule(&model, &video).map(|rule| rule.command);
+        assert_eq!(Some("mpv".to_owned()), rule);
+
+        let rule = super::get_opener_rule(&model, &png).map(|rule| rule.command);
+        assert_eq!(Some("imv".to_owned()), rule);
+
+        assert!(super::get_opener_rule(&model, dir).is_none());
+
+        assert!(super::is_mime_matching("*", "text/plain"));
+        assert!(super::is_mime_matching("text/plain", "text/plain"));
+        assert!(!super::is_mime_matching("video/*", "text/plain"));
+    }
+
+    #[test]
+    fn open_selected_text_preview() {
+        use std::fs;
+
+        use yeet_buffer::model::{ansi::Ansi, Buffer, BufferLine, Cursor};
+
+        use crate::{
+            action::Action,
+            model::{BufferType, Model},
+        };
+
+        let directory = tempfile::tempdir().expect("create temp dir");
+        let dir = directory.path();
+
+        let file = dir.join("index.html");
+        fs::write(&file, b"<html></html>").expect("write file");
+
+        let mut model = Model::default();
+        model.files.current.path = dir.to_path_buf();
+        model.files.current.buffer.lines = vec![BufferLine {
+            content: Ansi::new("index.html"),
+            ..Default::default()
+        }];
+        model.files.current_cursor = Some(Cursor::default());
+        model.files.preview = BufferType::Text(file.clone(), Buffer::default());
+
+        let actions = super::open_selected(&model);
+        assert!(matches!(actions.as_slice(), [Action::Open(path)] if path == &file));
+
+        model.settings.open_text_in_editor = true;
+
+        let actions = super::open_selected(&model);
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::OpenInForeground(path)] if path == &file
+        ));
+    }
+}
@@ -5,20 +5,26 @@ use std::{
 
 use yeet_buffer::{
     message::BufferMessage,
-    model::{ansi::Ansi, Buffer, BufferLine, Cursor, Mode},
+    model::{Buffer, Cursor, Mode},
     update::update_buffer,
 };
 
 use crate::{
     action::Action,
+    event::{ContentKind, DirectoryContent},
     model::{BufferType, Model, WindowType},
 };
 
 use super::{
+    column::set_columns,
+    enumeration::from_enumeration,
+    filter::{get_ignore_patterns, is_visible},
     history::get_selection_from_history,
-    junkyard::remove_from_junkyard,
+    junkbrowser,
+    junkyard::{is_junkyard_path, remove_from_junkyard},
     selection,
     sign::{set_sign_if_marked, set_sign_if_qfix},
+    sort::{get_sort_mode, sort_buffer},
 };
 
 #[tracing::instrument(skip(model))]
@@ -57,6 +63,10 @@ pub fn add_paths(model: &mut Model, paths: &[PathBuf]) -> Vec<Action> {
             continue;
         }
 
+        let patterns = get_ignore_patterns(&model.settings, path);
+        // NOTE: loaded by the enumeration task, thus directories without one are not ignored
+        let gitignore = model.files.gitignores.get(path).and_then(|it| it.as_ref());
+
         let mut selection = match cursor {
             Some(it) => get_selected_content_from_buffer(it, buffer),
             None => None,
@@ -80,7 +90,26 @@ pub fn add_paths(model: &mut Model, paths: &[PathBuf]) -> Vec<Action> {
 
         for path in paths_for_buffer {
             if let Some(basename) = path.file_name().and_then(|oss| oss.to_str()) {
-                let mut line = from(path);
+                let kind = ContentKind::new(path);
+                let is_dir = kind == ContentKind::Directory;
+                let metadata = path.symlink_metadata().ok();
+                let content = DirectoryContent {
+                    is_gitignored: gitignore.is_some_and(|it| it.is_ignored(path, is_dir)),
+                    ..DirectoryContent::new(basename.to_owned(), kind, metadata.as_ref())
+                };
+
+                let parent = path.parent().unwrap_or(path);
+                if let Some(contents) = model.files.enumerations.get_mut(parent) {
+                    contents.retain(|cntnt| cntnt.name != basename);
+                    contents.push(content.clone());
+                }
+
+                if !is_visible(&model.settings, &patterns, parent, &content) {
+                    continue;
+                }
+
+                let mut line = from_enumeration(&content.name, &content.kind);
+                set_columns(&model.settings, &mut model.owners, &mut line, &content);
                 set_sign_if_marked(&model.marks, &mut line, path);
                 set_sign_if_qfix(&model.qfix, &mut line, path);
 
@@ -101,12 +130,18 @@ pub fn add_paths(model: &mut Model, paths: &[PathBuf]) -> Vec<Action> {
         }
 
         if sort {
-            update_buffer(
+            let contents = match model.files.enumerations.get(path) {
+                Some(it) => it.as_slice(),
+                None => &[],
+            };
+
+            sort_buffer(
                 viewport,
                 cursor,
                 &model.mode,
                 buffer,
-                &BufferMessage::SortContent(super::SORT),
+                get_sort_mode(&model.files.sorts, &model.settings, path),
+                contents,
             );
         }
 
@@ -137,28 +172,17 @@ fn get_selected_content_from_buffer(cursor: &Cursor, model: &Buffer) -> Option<S
         .map(|line| line.content.to_stripped_string())
 }
 
-fn from(path: &Path) -> BufferLine {
-    let content = match path.file_name() {
-        Some(content) => content.to_str().unwrap_or("").to_string(),
-        None => "".to_string(),
-    };
-
-    let content = if path.is_dir() {
-        format!("\x1b[94m{}\x1b[39m", content)
-    } else {
-        content
-    };
-
-    BufferLine {
-        content: Ansi::new(&content),
-        ..Default::default()
-    }
-}
-
 #[tracing::instrument(skip(model))]
 pub fn remove_path(model: &mut Model, path: &Path) -> Vec<Action> {
-    if path.starts_with(&model.junk.path) {
+    if is_junkyard_path(&model.junk, path) {
         remove_from_junkyard(&mut model.junk, path);
+        junkbrowser::refresh(model);
+    }
+
+    if let (Some(parent), Some(basename)) = (path.parent(), path.file_name()) {
+        if let Some(contents) = model.files.enumerations.get_mut(parent) {
+            contents.retain(|cntnt| basename != cntnt.name.as_str());
+        }
     }
 
     let current_selection = match &model.files.current_cursor {
@@ -1,39 +1,234 @@
-use std::path::PathBuf;
+use std::path::Path;
+
+use yeet_buffer::{
+    message::{BufferMessage, ViewPortDirection},
+    model::Mode,
+    update::update_buffer,
+};
+use yeet_keymap::message::KeymapMessage;
 
 use crate::{
-    action::Action,
-    model::{qfix::QFIX_SIGN_ID, Model},
+    action::{self, Action},
+    model::{
+        qfix::{QuickFixEntry, QuickFixList, QFIX_SIGN_ID},
+        BufferType, Model,
+    },
 };
 
 use super::{
-    selection::{get_current_selected_bufferline, get_current_selected_path},
+    selection::{get_current_selected_range, get_selected_bufferline, get_selected_path},
     sign,
 };
 
 pub fn toggle_selected_to_qfix(model: &mut Model) -> Vec<Action> {
-    let selected = get_current_selected_path(model);
+    if let Some((start, end)) = get_current_selected_range(model) {
+        for index in start..=end {
+            toggle_qfix_at(model, index);
+        }
+
+        return vec![action::emit_keymap(KeymapMessage::Buffer(
+            BufferMessage::ChangeMode(model.mode.clone(), Mode::Navigation),
+        ))];
+    }
+
+    if let Some(cursor) = &model.files.current_cursor {
+        toggle_qfix_at(model, cursor.vertical_index);
+    }
+    Vec::new()
+}
+
+fn toggle_qfix_at(model: &mut Model, index: usize) {
+    let selected = get_selected_path(model, index);
     if let Some(selected) = selected {
-        if model.qfix.entries.contains(&selected) {
-            model.qfix.entries.retain(|p| p != &selected);
-            if let Some(bl) = get_current_selected_bufferline(model) {
+        let qfix = model.qfix.current_mut();
+        if qfix.contains(&selected) {
+            qfix.remove(&selected);
+            if let Some(bl) = get_selected_bufferline(model, index) {
                 sign::unset(bl, QFIX_SIGN_ID);
             }
         } else {
-            model.qfix.entries.push(selected);
-            if let Some(bl) = get_current_selected_bufferline(model) {
+            qfix.entries.push(QuickFixEntry::from(selected));
+            if let Some(bl) = get_selected_bufferline(model, index) {
                 sign::set(bl, QFIX_SIGN_ID);
             }
         }
     }
+}
+
+pub fn add(model: &mut Model, title: String, entries: Vec<QuickFixEntry>) -> Vec<Action> {
+    let mut list = QuickFixList::new(&title);
+    for entry in entries {
+        if !list.entries.contains(&entry) {
+            list.entries.push(entry);
+        };
+    }
+
+    model.qfix.push(list);
+    set_signs_for_current_list(model);
+
     Vec::new()
 }
 
-pub fn add(model: &mut Model, paths: Vec<PathBuf>) -> Vec<Action> {
-    for path in paths {
-        if !model.qfix.entries.contains(&path) {
-            sign::set_sign_for_path(model, path.as_path(), QFIX_SIGN_ID);
-            model.qfix.entries.push(path);
+pub fn append(model: &mut Model, entries: Vec<QuickFixEntry>) -> Vec<Action> {
+    let list = model.qfix.current_mut();
+    for entry in entries {
+        if !list.entries.contains(&entry) {
+            list.entries.push(entry);
         };
     }
+
+    set_signs_for_current_list(model);
+
     Vec::new()
 }
+
+pub fn highlight_location_in_preview(model: &mut Model, path: &Path) {
+    let qfix = model.qfix.current();
+    let location = match qfix.entries.get(qfix.current_index) {
+        Some(QuickFixEntry {
+            path: entry_path,
+            location: Some(location),
+        }) if entry_path == path => location.clone(),
+        _ => return,
+    };
+
+    let buffer = match &mut model.files.preview {
+        BufferType::Text(_, buffer) => buffer,
+        _ => return,
+    };
+
+    let index = location.line.saturating_sub(1);
+    if let Some(line) = buffer.lines.get_mut(index) {
+        // NOTE: rg columns are byte based and one indexed
+        let content = line.content.to_stripped_string();
+        let start = content
+            .get(..location.column.saturating_sub(1))
+            .map_or(0, |prefix| prefix.chars().count());
+
+        line.search_char_position = Some(vec![(start, location.text.chars().count())]);
+    } else {
+        return;
+    }
+
+    if let Some(cursor) = model.files.preview_cursor.as_mut() {
+        cursor.vertical_index = index;
+    }
+
+    update_buffer(
+        &mut model.files.preview_vp,
+        &mut model.files.preview_cursor,
+        &model.mode,
+        buffer,
+        &BufferMessage::MoveViewPort(ViewPortDirection::CenterOnCursor),
+    );
+}
+
+pub fn set_signs_for_current_list(model: &mut Model) {
+    sign::unset_sign_on_all_buffers(model, QFIX_SIGN_ID);
+    for path in model.qfix.current().paths() {
+        sign::set_sign_for_path(model, path.as_path(), QFIX_SIGN_ID);
+    }
+}
+
+mod test {
+    #[test]
+    fn add_creates_new_list_and_truncates_newer_lists() {
+        use std::path::PathBuf;
+
+        use crate::model::{qfix::QFIX_MAX_LISTS, Model};
+
+        let mut model = Model::default();
+        model
+            .qfix
+            .current_mut()
+            .entries
+            .push(PathBuf::from("/manual").into());
+
+        let paths = vec![
+            PathBuf::from("/a").into(),
+            PathBuf::from("/b").into(),
+            PathBuf::from("/a").into(),
+        ];
+        super::add(&mut model, "fd a".to_owned(), paths);
+
+        assert_eq!(2, model.qfix.lists.len());
+        assert_eq!(1, model.qfix.current_list);
+        assert_eq!("fd a", model.qfix.current().title);
+        assert_eq!(2, model.qfix.current().entries.len());
+        assert_eq!(1, model.qfix.lists[0].entries.len());
+
+        model.qfix.current_list = 0;
+        super::add(
+            &mut model,
+            "fd c".to_owned(),
+            vec![PathBuf::from("/c").into()],
+        );
+
+        assert_eq!(2, model.qfix.lists.len());
+        assert_eq!("fd c", model.qfix.lists[1].title);
+
+        for index in 0..QFIX_MAX_LISTS {
+            super::add(&mut model, format!("fd {}", index), Vec::new());
+        }
+
+        assert_eq!(QFIX_MAX_LISTS, model.qfix.lists.len());
+        assert_eq!(QFIX_MAX_LISTS - 1, model.qfix.current_list);
+        assert_eq!("fd 0", model.qfix.lists[0].title);
+    }
+
+    #[test]
+    fn highlight_location_in_preview() {
+        use std::path::PathBuf;
+
+        use yeet_buffer::model::{ansi::Ansi, Buffer, BufferLine};
+
+        use crate::model::{
+            qfix::{QuickFixEntry, QuickFixLocation},
+            BufferType, Model,
+        };
+
+        let path = PathBuf::from("/src/main.rs");
+        let mut model = Model::default();
+        model.files.preview_cursor = Some(Default::default());
+        model.files.preview_vp.height = 10;
+        model.files.preview = BufferType::Text(
+            path.clone(),
+            Buffer {
+                lines: ["use std;", "", "fn main() {}"]
+                    .iter()
+                    .map(|line| BufferLine {
+                        content: Ansi::new(line),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+        );
+
+        let entry = QuickFixEntry {
+            path: path.clone(),
+            location: Some(QuickFixLocation {
+                line: 3,
+                column: 4,
+                text: "main".to_owned(),
+            }),
+        };
+        super::add(&mut model, "rg main".to_owned(), vec![entry]);
+        super::highlight_location_in_preview(&mut model, &path);
+
+        let buffer = match &model.files.preview {
+            BufferType::Text(_, buffer) => buffer,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(Some(vec![(3, 4)]), buffer.lines[2].search_char_position);
+        assert_eq!(None, buffer.lines[0].search_char_position);
+        assert_eq!(
+            Some(2),
+            model
+                .files
+                .preview_cursor
+                .map(|cursor| cursor.vertical_index)
+        );
+    }
+}
@@ -0,0 +1,424 @@
+use std::path::PathBuf;
+
+use yeet_buffer::{
+    message::{BufferMessage, TextModification},
+    model::{ansi::Ansi, viewport::LineNumber, viewport::ViewPort, BufferLine, Cursor, Mode},
+    update::{focus_buffer, unfocus_buffer, update_buffer},
+};
+use yeet_keymap::message::KeymapMessage;
+
+use crate::{
+    action::Action,
+    event::Message,
+    model::{
+        qfix::{QuickFixEditor, QuickFixEntry, QuickFixLocation},
+        Model, WindowType,
+    },
+};
+
+use super::{
+    history, mode::change_mode, qfix::set_signs_for_current_list, selection,
+    viewport::set_viewport_dimensions,
+};
+
+pub fn open(model: &mut Model) -> Vec<Action> {
+    // NOTE: both replace the current window, thus only one can be open at a time
+    model.junk_browser = None;
+    model.qfix_editor = Some(QuickFixEditor {
+        cursor: Some(Cursor::default()),
+        viewport: ViewPort {
+            line_number: LineNumber::Relative,
+            line_number_width: 3,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    refresh(model);
+    load_preview(model)
+}
+
+pub fn close(model: &mut Model) -> Vec<Action> {
+    model.qfix_editor = None;
+
+    let mut actions = Vec::new();
+    if let Some(path) = selection::get_current_selected_path(model) {
+        let selection =
+            history::get_selection_from_history(&model.history, &path).map(|s| s.to_owned());
+        actions.push(Action::Load(WindowType::Preview, path, selection));
+    }
+
+    actions
+}
+
+fn refresh(model: &mut Model) {
+    let editor = match model.qfix_editor.as_mut() {
+        Some(it) => it,
+        None => return,
+    };
+
+    let qfix = model.qfix.current();
+    editor.entries = qfix.entries.clone();
+
+    let lines = editor
+        .entries
+        .iter()
+        .map(|entry| BufferLine {
+            content: Ansi::new(&entry.to_string()),
+            ..Default::default()
+        })
+        .collect();
+
+    set_viewport_dimensions(&mut editor.viewport, &model.layout.current);
+    update_buffer(
+        &mut editor.viewport,
+        &mut editor.cursor,
+        &model.mode,
+        &mut editor.buffer,
+        &BufferMessage::SetContent(lines),
+    );
+}
+
+pub fn update(model: &mut Model, msg: &KeymapMessage) -> Option<Vec<Action>> {
+    // NOTE: mode changes must reach the editor cursor, even when leaving the commandline
+    let is_change_mode = matches!(msg, KeymapMessage::Buffer(BufferMessage::ChangeMode(_, _)));
+    if model.qfix_editor.is_none() || (model.mode.is_command() && !is_change_mode) {
+        return None;
+    }
+
+    let actions = match msg {
+        KeymapMessage::Buffer(BufferMessage::ChangeMode(from, to)) => {
+            let actions = change_mode(model, from, to);
+            if let Some(editor) = model.qfix_editor.as_mut() {
+                if to.is_command() {
+                    unfocus_buffer(&mut editor.cursor);
+                } else {
+                    focus_buffer(&mut editor.cursor);
+                    update_editor(model, &BufferMessage::ChangeMode(from.clone(), to.clone()));
+                }
+            }
+            actions
+        }
+        KeymapMessage::Buffer(BufferMessage::Modification(repeat, modification)) => {
+            match model.mode {
+                Mode::Insert | Mode::Normal => {
+                    let msg = BufferMessage::Modification(*repeat, modification.clone());
+                    update_editor(model, &msg);
+                    Vec::new()
+                }
+                Mode::VisualLine => modify_selection(model, modification),
+                Mode::Command(_) | Mode::Navigation => Vec::new(),
+            }
+        }
+        KeymapMessage::Buffer(
+            msg @ (BufferMessage::MoveCursor(_, _) | BufferMessage::MoveViewPort(_)),
+        ) => move_cursor(model, msg),
+        KeymapMessage::Buffer(msg @ (BufferMessage::Redo | BufferMessage::Undo)) => {
+            if model.mode == Mode::Normal {
+                update_editor(model, msg);
+            }
+            Vec::new()
+        }
+        KeymapMessage::Buffer(BufferMessage::SaveBuffer) => save(model),
+        KeymapMessage::NavigateToMark(_)
+        | KeymapMessage::NavigateToPath(_)
+        | KeymapMessage::NavigateToPathAsPreview(_) => {
+            model.qfix_editor = None;
+            return None;
+        }
+        KeymapMessage::NavigateToParent => close(model),
+        KeymapMessage::NavigateToSelected | KeymapMessage::OpenSelected => {
+            match get_selected_entry(model) {
+                Some(entry) => {
+                    model.qfix_editor = None;
+                    vec![Action::EmitMessages(vec![Message::Keymap(
+                        KeymapMessage::NavigateToPathAsPreview(entry.path),
+                    )])]
+                }
+                None => Vec::new(),
+            }
+        }
+        KeymapMessage::PasteFromJunkYard(_)
+        | KeymapMessage::SetMark(_)
+        | KeymapMessage::ToggleQuickFix
+        | KeymapMessage::YankPathToClipboard
+        | KeymapMessage::YankToJunkYard(_) => Vec::new(),
+        _ => return None,
+    };
+
+    Some(actions)
+}
+
+pub fn save(model: &mut Model) -> Vec<Action> {
+    let editor = match model.qfix_editor.as_mut() {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    update_buffer(
+        &mut editor.viewport,
+        &mut editor.cursor,
+        &model.mode,
+        &mut editor.buffer,
+        &BufferMessage::SaveBuffer,
+    );
+
+    let mut entries: Vec<QuickFixEntry> = Vec::new();
+    for line in editor.buffer.lines.iter() {
+        let content = line.content.to_stripped_string();
+        if content.trim().is_empty() {
+            continue;
+        }
+
+        let entry = match editor
+            .entries
+            .iter()
+            .find(|entry| entry.to_string() == content)
+        {
+            Some(entry) => entry.clone(),
+            None => to_entry(&content),
+        };
+
+        if !entries.contains(&entry) {
+            entries.push(entry);
+        }
+    }
+
+    let qfix = model.qfix.current_mut();
+    let current = qfix.entries.get(qfix.current_index).cloned();
+    qfix.current_index = current
+        .and_then(|current| entries.iter().position(|entry| entry == &current))
+        .unwrap_or(0);
+    qfix.entries = entries;
+
+    set_signs_for_current_list(model);
+    refresh(model);
+
+    Vec::new()
+}
+
+fn update_editor(model: &mut Model, msg: &BufferMessage) {
+    if let Some(editor) = model.qfix_editor.as_mut() {
+        set_viewport_dimensions(&mut editor.viewport, &model.layout.current);
+        update_buffer(
+            &mut editor.viewport,
+            &mut editor.cursor,
+            &model.mode,
+            &mut editor.buffer,
+            msg,
+        );
+    }
+}
+
+fn modify_selection(model: &mut Model, modification: &TextModification) -> Vec<Action> {
+    let cursor = match model.qfix_editor.as_mut().and_then(|e| e.cursor.as_mut()) {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    let (start, end) = match cursor.anchor {
+        Some(anchor) if anchor < cursor.vertical_index => (anchor, cursor.vertical_index),
+        Some(anchor) => (cursor.vertical_index, anchor),
+        None => return Vec::new(),
+    };
+
+    cursor.vertical_index = start;
+    update_editor(model, &BufferMessage::UpdateViewPortByCursor);
+
+    vec![Action::EmitMessages(vec![
+        Message::Keymap(KeymapMessage::Buffer(BufferMessage::ChangeMode(
+            model.mode.clone(),
+            Mode::Normal,
+        ))),
+        Message::Keymap(KeymapMessage::Buffer(BufferMessage::Modification(
+            end - start + 1,
+            modification.clone(),
+        ))),
+    ])]
+}
+
+fn move_cursor(model: &mut Model, msg: &BufferMessage) -> Vec<Action> {
+    let selected = get_selected_entry(model);
+    update_editor(model, msg);
+
+    if selected == get_selected_entry(model) {
+        Vec::new()
+    } else {
+        load_preview(model)
+    }
+}
+
+fn load_preview(model: &Model) -> Vec<Action> {
+    match get_selected_entry(model) {
+        Some(entry) => vec![Action::Load(WindowType::Preview, entry.path, None)],
+        None => Vec::new(),
+    }
+}
+
+fn get_selected_entry(model: &Model) -> Option<QuickFixEntry> {
+    let editor = model.qfix_editor.as_ref()?;
+    let cursor = editor.cursor.as_ref()?;
+    let line = editor.buffer.lines.get(cursor.vertical_index)?;
+
+    let content = line.content.to_stripped_string();
+    if content.trim().is_empty() {
+        return None;
+    }
+
+    match editor
+        .entries
+        .iter()
+        .find(|entry| entry.to_string() == content)
+    {
+        Some(entry) => Some(entry.clone()),
+        None => Some(to_entry(&content)),
+    }
+}
+
+fn to_entry(content: &str) -> QuickFixEntry {
+    let content = content.trim();
+    if let Some((position, text)) = content.split_once(' ') {
+        let mut parts = position.rsplitn(3, ':');
+        if let (Some(column), Some(line), Some(path)) = (parts.next(), parts.next(), parts.next()) {
+            if let (Ok(line), Ok(column)) = (line.parse(), column.parse()) {
+                return QuickFixEntry {
+                    path: PathBuf::from(path),
+                    location: Some(QuickFixLocation {
+                        line,
+                        column,
+                        text: text.to_owned(),
+                    }),
+                };
+            }
+        }
+    }
+
+    PathBuf::from(content).into()
+}
+
+mod test {
+    #[test]
+    fn save_commits_reordered_and_edited_lines() {
+        use std::path::PathBuf;
+
+        use ratatui::layout::Rect;
+
+        use yeet_buffer::{
+            message::{BufferMessage, CursorDirection, TextModification},
+            model::Mode,
+        };
+        use yeet_keymap::message::KeymapMessage;
+
+        use crate::model::{
+            qfix::{QuickFixEntry, QuickFixLocation},
+            Model,
+        };
+
+        let mut model = Model {
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+        model.layout.current = Rect::new(0, 0, 80, 20);
+
+        let located = QuickFixEntry {
+            path: PathBuf::from("/src/main.rs"),
+            location: Some(QuickFixLocation {
+                line: 3,
+                column: 4,
+                text: "main".to_owned(),
+            }),
+        };
+        let qfix = model.qfix.current_mut();
+        qfix.entries = vec![
+            PathBuf::from("/a").into(),
+            located.clone(),
+            PathBuf::from("/b").into(),
+        ];
+        qfix.current_index = 1;
+
+        assert!(super::update(&mut model, &KeymapMessage::NavigateToParent).is_none());
+
+        super::open(&mut model);
+        let editor = model.qfix_editor.as_ref().expect("editor is open");
+        assert_eq!(3, editor.buffer.lines.len());
+        assert_eq!(
+            "/src/main.rs:3:4 main",
+            editor.buffer.lines[1].content.to_stripped_string()
+        );
+
+        let msg =
+            KeymapMessage::Buffer(BufferMessage::Modification(1, TextModification::DeleteLine));
+        super::update(&mut model, &msg).expect("handled by editor");
+
+        let msg = KeymapMessage::Buffer(BufferMessage::MoveCursor(1, CursorDirection::Bottom));
+        super::update(&mut model, &msg).expect("handled by editor");
+
+        let msg = KeymapMessage::Buffer(BufferMessage::Modification(
+            1,
+            TextModification::InsertNewLine(yeet_buffer::message::LineDirection::Down),
+        ));
+        super::update(&mut model, &msg).expect("handled by editor");
+
+        model.mode = Mode::Insert;
+        let msg = KeymapMessage::Buffer(BufferMessage::Modification(
+            1,
+            TextModification::Insert("/c:7:1 fn".to_owned()),
+        ));
+        super::update(&mut model, &msg).expect("handled by editor");
+        model.mode = Mode::Normal;
+
+        assert_eq!(3, model.qfix.current().entries.len());
+
+        super::update(
+            &mut model,
+            &KeymapMessage::Buffer(BufferMessage::SaveBuffer),
+        );
+
+        let qfix = model.qfix.current();
+        assert_eq!(
+            vec![
+                located,
+                PathBuf::from("/b").into(),
+                QuickFixEntry {
+                    path: PathBuf::from("/c"),
+                    location: Some(QuickFixLocation {
+                        line: 7,
+                        column: 1,
+                        text: "fn".to_owned(),
+                    }),
+                },
+            ],
+            qfix.entries
+        );
+        assert_eq!(0, qfix.current_index);
+
+        super::update(&mut model, &KeymapMessage::NavigateToParent);
+        assert!(model.qfix_editor.is_none());
+    }
+
+    #[test]
+    fn open_closes_junk_browser() {
+        use ratatui::layout::Rect;
+
+        use yeet_buffer::model::Mode;
+
+        use crate::{model::Model, update::junkbrowser};
+
+        let mut model = Model {
+            mode: Mode::Navigation,
+            ..Default::default()
+        };
+        model.layout.current = Rect::new(0, 0, 80, 20);
+
+        junkbrowser::open(&mut model);
+        assert!(model.junk_browser.is_some());
+
+        super::open(&mut model);
+        assert!(model.junk_browser.is_none());
+        assert!(model.qfix_editor.is_some());
+
+        junkbrowser::open(&mut model);
+        assert!(model.junk_browser.is_some());
+        assert!(model.qfix_editor.is_none());
+    }
+}
@@ -0,0 +1,12 @@
+use crate::{model::Model, remote::RemoteState};
+
+use super::selection;
+
+pub fn get_state(model: &Model) -> RemoteState {
+    RemoteState {
+        current: model.files.current.path.clone(),
+        mode: model.mode.to_string(),
+        qfix: model.qfix.current().paths(),
+        selected: selection::get_current_selected_path(model),
+    }
+}
@@ -7,9 +7,16 @@ use yeet_buffer::{
     update::update_buffer,
 };
 
-use crate::{action::Action, model::Model, task::Task};
+use crate::{
+    action::Action,
+    model::{conflict::ConflictPolicy, junkyard::TrashBackend, Model},
+    task::Task,
+};
 
-use super::{junkyard::trash_to_junkyard, selection::get_current_selected_bufferline};
+use super::{
+    junkyard::{evict_from_junkyard, get_delete_actions, trash_to_junkyard},
+    selection::get_current_selected_bufferline,
+};
 
 #[tracing::instrument(skip(model))]
 pub fn persist_path_changes(model: &mut Model) -> Vec<Action> {
@@ -68,6 +75,7 @@ pub fn persist_path_changes(model: &mut Model) -> Vec<Action> {
                             Task::RenamePath(
                                 path.join(old_name.to_stripped_string()),
                                 path.join(new_name.to_stripped_string()),
+                                ConflictPolicy::Fail,
                             )
                         };
                         actions.push(Action::Task(task));
@@ -76,16 +84,24 @@ pub fn persist_path_changes(model: &mut Model) -> Vec<Action> {
             }
 
             if !trashes.is_empty() {
-                let (transaction, obsolete) = trash_to_junkyard(&mut model.junk, trashes);
+                let backend = &model.settings.trash_backend;
+                let transaction = trash_to_junkyard(&mut model.junk, trashes, backend);
                 for entry in transaction.entries {
-                    actions.push(Action::Task(Task::TrashPath(entry)));
-                }
+                    let task =
+                        if backend == &TrashBackend::JunkYard && model.settings.junkyard.compress {
+                            Task::Sequence(vec![
+                                Task::TrashPath(entry.clone()),
+                                Task::CompressJunkYardEntry(entry),
+                            ])
+                        } else {
+                            Task::TrashPath(entry)
+                        };
 
-                if let Some(obsolete) = obsolete {
-                    for entry in obsolete.entries {
-                        actions.push(Action::Task(Task::DeleteJunkYardEntry(entry)));
-                    }
+                    actions.push(Action::Task(task));
                 }
+
+                let obsolete = evict_from_junkyard(&mut model.junk, &model.settings.junkyard);
+                actions.extend(get_delete_actions(obsolete));
             }
         }
     }
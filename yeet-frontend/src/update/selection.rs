@@ -5,13 +5,36 @@ use yeet_buffer::model::BufferLine;
 use crate::{action::Action, event::Message, model::Model};
 
 pub fn get_current_selected_path(model: &Model) -> Option<PathBuf> {
+    let cursor = &model.files.current_cursor.as_ref()?;
+    get_selected_path(model, cursor.vertical_index)
+}
+
+pub fn get_current_selected_range(model: &Model) -> Option<(usize, usize)> {
+    let cursor = &model.files.current_cursor.as_ref()?;
+    let anchor = cursor.anchor?;
+    if anchor < cursor.vertical_index {
+        Some((anchor, cursor.vertical_index))
+    } else {
+        Some((cursor.vertical_index, anchor))
+    }
+}
+
+pub fn get_current_selected_range_paths(model: &Model) -> Vec<PathBuf> {
+    match get_current_selected_range(model) {
+        Some((start, end)) => (start..=end)
+            .filter_map(|index| get_selected_path(model, index))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+pub fn get_selected_path(model: &Model, index: usize) -> Option<PathBuf> {
     let buffer = &model.files.current.buffer;
     if buffer.lines.is_empty() {
         return None;
     }
 
-    let cursor = &model.files.current_cursor.as_ref()?;
-    let current = &buffer.lines.get(cursor.vertical_index)?;
+    let current = &buffer.lines.get(index)?;
     if current.content.is_empty() {
         return None;
     }
@@ -22,7 +45,8 @@ pub fn get_current_selected_path(model: &Model) -> Option<PathBuf> {
         .path
         .join(current.content.to_stripped_string());
 
-    if target.exists() {
+    // NOTE: symlink_metadata to keep broken links selectable
+    if target.symlink_metadata().is_ok() {
         Some(target)
     } else {
         None
@@ -30,13 +54,17 @@ pub fn get_current_selected_path(model: &Model) -> Option<PathBuf> {
 }
 
 pub fn get_current_selected_bufferline(model: &mut Model) -> Option<&mut BufferLine> {
+    let index = model.files.current_cursor.as_ref()?.vertical_index;
+    get_selected_bufferline(model, index)
+}
+
+pub fn get_selected_bufferline(model: &mut Model, index: usize) -> Option<&mut BufferLine> {
     let buffer = &mut model.files.current.buffer;
     if buffer.lines.is_empty() {
         return None;
     }
 
-    let cursor = &model.files.current_cursor.as_ref()?;
-    buffer.lines.get_mut(cursor.vertical_index)
+    buffer.lines.get_mut(index)
 }
 
 pub fn copy_current_selected_path_to_clipboard(model: &mut Model) -> Vec<Action> {
@@ -4,8 +4,9 @@ use crate::model::{mark::MARK_SIGN_ID, qfix::QFIX_SIGN_ID, Model};
 
 pub fn update_with_settings(model: &mut Model) {
     model.files.current_vp.set(&model.settings.current);
-    model.files.parent_vp.set(&model.settings.current);
-    model.files.preview_vp.set(&model.settings.current);
+    model.files.parent_vp.set(&model.settings.parent);
+    model.files.preview_vp.set(&model.settings.preview);
+    model.files.show_border = model.settings.show_border;
 
     if model.settings.show_mark_signs {
         remove_hidden_sign_on_all_buffer(model, &MARK_SIGN_ID);
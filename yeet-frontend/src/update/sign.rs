@@ -8,7 +8,7 @@ use crate::model::{
 };
 
 pub fn set_sign_if_qfix(qfix: &QuickFix, bl: &mut BufferLine, path: &Path) {
-    let is_marked = qfix.entries.iter().any(|p| p == path);
+    let is_marked = qfix.current().contains(path);
     if !is_marked {
         return;
     }
@@ -0,0 +1,257 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use yeet_buffer::{
+    message::BufferMessage,
+    model::{viewport::ViewPort, Buffer, Cursor, Mode},
+    update::update_buffer,
+};
+
+use crate::{
+    event::{ContentKind, DirectoryContent},
+    model::{
+        sort::{SortKind, SortMode},
+        Model,
+    },
+    settings::Settings,
+};
+
+pub fn get_sort_mode<'a>(
+    sorts: &'a HashMap<PathBuf, SortMode>,
+    settings: &'a Settings,
+    path: &Path,
+) -> &'a SortMode {
+    match sorts.get(path) {
+        Some(it) => it,
+        None => &settings.sort,
+    }
+}
+
+pub fn sort_buffer(
+    viewport: &mut ViewPort,
+    cursor: &mut Option<Cursor>,
+    mode: &Mode,
+    buffer: &mut Buffer,
+    sort: &SortMode,
+    contents: &[DirectoryContent],
+) {
+    let contents: HashMap<_, _> = contents
+        .iter()
+        .map(|cntnt| (cntnt.name.as_str(), cntnt))
+        .collect();
+
+    let mut lines: Vec<_> = std::mem::take(&mut buffer.lines)
+        .into_iter()
+        .map(|line| (line.content.to_stripped_string(), line))
+        .collect();
+
+    lines.sort_by(|(a, _), (b, _)| {
+        compare(
+            sort,
+            (a, contents.get(a.as_str()).copied()),
+            (b, contents.get(b.as_str()).copied()),
+        )
+    });
+
+    let lines = lines.into_iter().map(|(_, line)| line).collect();
+    update_buffer(
+        viewport,
+        cursor,
+        mode,
+        buffer,
+        &BufferMessage::SetContent(lines),
+    );
+}
+
+pub fn sort_directory(model: &mut Model, path: &Path, selection: Option<String>) {
+    let sort = get_sort_mode(&model.files.sorts, &model.settings, path).clone();
+    let contents = model.files.enumerations.get(path).cloned();
+
+    let directories = model.files.get_mut_directories();
+    if let Some((_, viewport, cursor, buffer)) =
+        directories.into_iter().find(|(p, _, _, _)| p == &path)
+    {
+        sort_buffer(
+            viewport,
+            cursor,
+            &model.mode,
+            buffer,
+            &sort,
+            &contents.unwrap_or_default(),
+        );
+
+        if let Some(selection) = selection {
+            update_buffer(
+                viewport,
+                cursor,
+                &model.mode,
+                buffer,
+                &BufferMessage::SetCursorToLineContent(selection),
+            );
+        }
+    }
+}
+
+fn compare(
+    sort: &SortMode,
+    (a_name, a): (&str, Option<&DirectoryContent>),
+    (b_name, b): (&str, Option<&DirectoryContent>),
+) -> Ordering {
+    let ordering = match sort.kind {
+        SortKind::DirectoriesFirst => is_directory(b)
+            .cmp(&is_directory(a))
+            .then_with(|| compare_natural(a_name, b_name)),
+        SortKind::Extension => get_extension(a_name)
+            .cmp(&get_extension(b_name))
+            .then_with(|| compare_name(a_name, b_name)),
+        SortKind::Modified => b
+            .and_then(|cntnt| cntnt.modified)
+            .cmp(&a.and_then(|cntnt| cntnt.modified))
+            .then_with(|| compare_name(a_name, b_name)),
+        SortKind::Name => compare_name(a_name, b_name),
+        SortKind::Natural => compare_natural(a_name, b_name),
+        SortKind::Size => b
+            .map(|cntnt| cntnt.size)
+            .cmp(&a.map(|cntnt| cntnt.size))
+            .then_with(|| compare_name(a_name, b_name)),
+    };
+
+    if sort.reverse {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
+
+fn compare_name(a: &str, b: &str) -> Ordering {
+    a.to_ascii_uppercase().cmp(&b.to_ascii_uppercase())
+}
+
+fn compare_natural(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    loop {
+        let ordering = match (a_chars.peek(), b_chars.peek()) {
+            (None, None) => return compare_name(a, b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_char), Some(b_char)) if a_char.is_ascii_digit() && b_char.is_ascii_digit() => {
+                let a_number = take_number(&mut a_chars);
+                let b_number = take_number(&mut b_chars);
+
+                a_number
+                    .len()
+                    .cmp(&b_number.len())
+                    .then_with(|| a_number.cmp(&b_number))
+            }
+            (Some(a_char), Some(b_char)) => {
+                let ordering = a_char
+                    .to_ascii_uppercase()
+                    .cmp(&b_char.to_ascii_uppercase());
+
+                a_chars.next();
+                b_chars.next();
+
+                ordering
+            }
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+fn take_number(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut number = String::new();
+    while let Some(char) = chars.next_if(|c| c.is_ascii_digit()) {
+        if number.is_empty() && char == '0' {
+            continue;
+        }
+        number.push(char);
+    }
+    number
+}
+
+fn get_extension(name: &str) -> Option<String> {
+    Path::new(name)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_ascii_lowercase())
+}
+
+fn is_directory(content: Option<&DirectoryContent>) -> bool {
+    content.is_some_and(|cntnt| cntnt.kind == ContentKind::Directory)
+}
+
+mod test {
+    #[test]
+    fn compare_natural() {
+        use std::cmp::Ordering;
+
+        assert_eq!(super::compare_natural("file2", "file10"), Ordering::Less);
+        assert_eq!(super::compare_natural("file10", "File9"), Ordering::Greater);
+        assert_eq!(super::compare_natural("a", "a1"), Ordering::Less);
+        assert_eq!(
+            super::compare_natural("v1.10.2", "v1.9.12"),
+            Ordering::Greater
+        );
+        assert_eq!(super::compare_natural("img007", "img7b"), Ordering::Less);
+    }
+
+    #[test]
+    fn compare() {
+        use std::{cmp::Ordering, time::SystemTime};
+
+        use crate::{
+            event::{ContentKind, DirectoryContent},
+            model::sort::{SortKind, SortMode},
+        };
+
+        let content = |name: &str, kind: ContentKind, size: u64| DirectoryContent {
+            is_gitignored: false,
+            kind,
+            modified: Some(SystemTime::UNIX_EPOCH),
+            name: name.to_owned(),
+            permissions: None,
+            size,
+            uid: None,
+        };
+
+        let directory = content("zeta", ContentKind::Directory, 0);
+        let small = content("alpha.txt", ContentKind::File, 10);
+        let large = content("beta.rs", ContentKind::File, 20);
+
+        let compare =
+            |kind: SortKind, reverse: bool, a: &DirectoryContent, b: &DirectoryContent| {
+                super::compare(
+                    &SortMode { kind, reverse },
+                    (&a.name, Some(a)),
+                    (&b.name, Some(b)),
+                )
+            };
+
+        assert_eq!(
+            compare(SortKind::DirectoriesFirst, false, &small, &directory),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare(SortKind::Size, false, &small, &large),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare(SortKind::Size, true, &small, &large),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare(SortKind::Extension, false, &small, &large),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare(SortKind::Name, true, &small, &large),
+            Ordering::Greater
+        );
+    }
+}
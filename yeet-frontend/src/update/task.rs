@@ -4,6 +4,7 @@ use tokio_util::sync::CancellationToken;
 
 use crate::{
     action::Action,
+    event::TaskProgress,
     model::{CurrentTask, Model},
 };
 
@@ -16,6 +17,7 @@ pub fn add(model: &mut Model, identifier: String, cancellation: CancellationToke
             token: cancellation,
             id,
             external_id: identifier,
+            progress: None,
         },
     ) {
         replaced_task.token.cancel();
@@ -46,6 +48,17 @@ fn next_id(model: &mut Model) -> u16 {
     next_id
 }
 
+pub fn update_progress(
+    model: &mut Model,
+    identifier: String,
+    progress: TaskProgress,
+) -> Vec<Action> {
+    if let Some(task) = model.current_tasks.get_mut(&identifier) {
+        task.progress = Some(progress);
+    }
+    Vec::new()
+}
+
 pub fn remove(model: &mut Model, identifier: String) -> Vec<Action> {
     if let Some(task) = model.current_tasks.remove(&identifier) {
         task.token.cancel();
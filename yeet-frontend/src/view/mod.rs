@@ -20,25 +20,68 @@ pub fn render_model(terminal: &mut TerminalWrapper, model: &Model) -> Result<(),
 
         commandline::view(model, frame);
 
-        view::view(
-            &model.files.current_vp,
-            &model.files.current_cursor,
-            &model.mode,
-            &model.files.current.buffer,
-            &model.files.show_border,
-            frame,
-            layout.current,
-        );
+        // NOTE: the junk yard browser and qfix editor replace the current directory while open
+        if let Some(browser) = &model.junk_browser {
+            view::view(
+                &browser.viewport,
+                &browser.cursor,
+                &model.mode,
+                &browser.buffer,
+                &model.files.show_border,
+                frame,
+                layout.current,
+            );
 
-        render_buffer(
-            &model.files.parent_vp,
-            &model.files.parent_cursor,
-            &model.mode,
-            frame,
-            layout.parent,
-            &model.files.parent,
-            &model.files.show_border,
-        );
+            render_buffer(
+                &model.files.parent_vp,
+                &None,
+                &model.mode,
+                frame,
+                layout.parent,
+                &BufferType::None,
+                &model.files.show_border,
+            );
+        } else if let Some(editor) = &model.qfix_editor {
+            view::view(
+                &editor.viewport,
+                &editor.cursor,
+                &model.mode,
+                &editor.buffer,
+                &model.files.show_border,
+                frame,
+                layout.current,
+            );
+
+            render_buffer(
+                &model.files.parent_vp,
+                &None,
+                &model.mode,
+                frame,
+                layout.parent,
+                &BufferType::None,
+                &model.files.show_border,
+            );
+        } else {
+            view::view(
+                &model.files.current_vp,
+                &model.files.current_cursor,
+                &model.mode,
+                &model.files.current.buffer,
+                &model.files.show_border,
+                frame,
+                layout.current,
+            );
+
+            render_buffer(
+                &model.files.parent_vp,
+                &model.files.parent_cursor,
+                &model.mode,
+                frame,
+                layout.parent,
+                &model.files.parent,
+                &model.files.show_border,
+            );
+        }
         render_buffer(
             &model.files.preview_vp,
             &model.files.preview_cursor,
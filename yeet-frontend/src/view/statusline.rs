@@ -12,6 +12,7 @@ use crate::model::Model;
 pub fn view(model: &Model, frame: &mut Frame, rect: Rect) {
     let changes = get_changes_content(model);
     let position = get_position_content(model);
+    let progress = get_progress_content(model);
 
     let content = model.files.current.path.to_str().unwrap_or("");
     let style = Style::default().fg(Color::Gray);
@@ -24,6 +25,7 @@ pub fn view(model: &Model, frame: &mut Frame, rect: Rect) {
             Constraint::Length(path.width() as u16),
             Constraint::Length(3),
             Constraint::Min(changes.width() as u16),
+            Constraint::Length(progress.width() as u16),
             Constraint::Length(position.width() as u16),
         ])
         .split(rect);
@@ -35,7 +37,33 @@ pub fn view(model: &Model, frame: &mut Frame, rect: Rect) {
 
     frame.render_widget(Paragraph::new(path), layout[0]);
     frame.render_widget(Paragraph::new(changes), layout[2]);
-    frame.render_widget(Paragraph::new(position), layout[3]);
+    frame.render_widget(Paragraph::new(progress), layout[3]);
+    frame.render_widget(Paragraph::new(position), layout[4]);
+}
+
+fn get_progress_content(model: &Model) -> Line<'static> {
+    let mut tasks: Vec<_> = model
+        .current_tasks
+        .values()
+        .filter_map(|task| task.progress.as_ref().map(|progress| (task.id, progress)))
+        .collect();
+
+    if tasks.is_empty() {
+        return Line::default();
+    }
+
+    tasks.sort_by_key(|(id, _)| *id);
+
+    let content = tasks
+        .iter()
+        .map(|(id, progress)| format!("[{}] {}", id, progress.to_compact_string()))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Line::from(Span::styled(
+        format!("{} ", content),
+        Style::default().fg(Color::Gray),
+    ))
 }
 
 fn get_position_content(model: &Model) -> Line {
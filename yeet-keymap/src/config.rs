@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde::Deserialize;
+use yeet_buffer::{
+    message::{BufferMessage, CursorDirection, Search, TextModification, ViewPortDirection},
+    model::{CommandMode, Mode, SearchDirection},
+};
+
+use crate::{
+    key::Key,
+    message::{Binding, BindingKind, KeymapMessage, NextBindingKind, QuitMode},
+    KeyMapError,
+};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct KeyMapConfig {
+    command: HashMap<String, MappingConfig>,
+    insert: HashMap<String, MappingConfig>,
+    navigation: HashMap<String, MappingConfig>,
+    normal: HashMap<String, MappingConfig>,
+    visual_line: HashMap<String, MappingConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum MappingConfig {
+    Command(String),
+    Message(String),
+    Mode(String),
+    Motion(String),
+    None,
+}
+
+pub struct UserMapping {
+    pub binding: Option<Binding>,
+    pub keys: Vec<Key>,
+    pub modes: Vec<Mode>,
+}
+
+pub fn from_toml(content: &str) -> (Vec<UserMapping>, Vec<KeyMapError>) {
+    let config: KeyMapConfig = match toml::from_str(content) {
+        Ok(it) => it,
+        Err(err) => {
+            return (
+                Vec::new(),
+                vec![KeyMapError::ConfigurationInvalid(err.message().to_owned())],
+            )
+        }
+    };
+
+    let modes = vec![
+        (
+            vec![
+                Mode::Command(CommandMode::Command),
+                Mode::Command(CommandMode::Search(SearchDirection::Up)),
+                Mode::Command(CommandMode::Search(SearchDirection::Down)),
+            ],
+            config.command,
+        ),
+        (vec![Mode::Insert], config.insert),
+        (vec![Mode::Navigation], config.navigation),
+        (vec![Mode::Normal], config.normal),
+        (vec![Mode::VisualLine], config.visual_line),
+    ];
+
+    let mut mappings = Vec::new();
+    let mut errors = Vec::new();
+    for (modes, config_mappings) in modes {
+        for (sequence, mapping) in config_mappings {
+            let keys = match to_keys(&sequence) {
+                Ok(it) => it,
+                Err(err) => {
+                    errors.push(err);
+                    continue;
+                }
+            };
+
+            let binding = match to_binding(mapping) {
+                Ok(it) => it,
+                Err(err) => {
+                    errors.push(err);
+                    continue;
+                }
+            };
+
+            mappings.push(UserMapping {
+                binding,
+                keys,
+                modes: modes.clone(),
+            });
+        }
+    }
+
+    (mappings, errors)
+}
+
+fn to_keys(sequence: &str) -> Result<Vec<Key>, KeyMapError> {
+    let regex = Regex::new(r"<[^>]*>|.").expect("Failed to compile regex");
+    let keys = regex
+        .find_iter(sequence)
+        .map(|m| Key::from_keycode_string(m.as_str()))
+        .collect::<Option<Vec<_>>>();
+
+    match keys {
+        Some(keys) if !keys.is_empty() => Ok(keys),
+        _ => Err(KeyMapError::KeySequenceInvalid(sequence.to_owned())),
+    }
+}
+
+fn to_binding(mapping: MappingConfig) -> Result<Option<Binding>, KeyMapError> {
+    let binding = match mapping {
+        MappingConfig::Command(command) => Binding {
+            kind: BindingKind::Message(KeymapMessage::ExecuteCommandString(command)),
+            ..Default::default()
+        },
+        MappingConfig::Message(name) => to_message_binding(&name)?,
+        MappingConfig::Mode(name) => Binding {
+            force: Some(to_mode(&name)?),
+            repeatable: false,
+            ..Default::default()
+        },
+        MappingConfig::Motion(name) => to_motion_binding(&name)?,
+        MappingConfig::None => return Ok(None),
+    };
+
+    Ok(Some(binding))
+}
+
+fn to_message_binding(name: &str) -> Result<Binding, KeyMapError> {
+    let expects_char = Some(NextBindingKind::Raw(Some(
+        Regex::new("[[:alpha:]]").expect("Invalid regex"),
+    )));
+
+    let (message, expects) = match name {
+        "clear_search_highlight" => (KeymapMessage::ClearSearchHighlight, None),
+        "delete_line" => {
+            return Ok(Binding {
+                kind: BindingKind::Modification(TextModification::DeleteLine),
+                ..Default::default()
+            })
+        }
+        "execute_command" => (KeymapMessage::ExecuteCommand, None),
+        "leave_command_mode" => (KeymapMessage::LeaveCommandMode, None),
+        "navigate_to_mark" => (
+            KeymapMessage::NavigateToMark(' '),
+            Some(NextBindingKind::Raw(None)),
+        ),
+        "navigate_to_parent" => (KeymapMessage::NavigateToParent, None),
+        "navigate_to_selected" => (KeymapMessage::NavigateToSelected, None),
+        "open_selected" => (KeymapMessage::OpenSelected, None),
+        "paste_from_junkyard" => (
+            KeymapMessage::PasteFromJunkYard(' '),
+            Some(NextBindingKind::Raw(None)),
+        ),
+        "quit" => (KeymapMessage::Quit(QuitMode::FailOnRunningTasks), None),
+        "quit_force" => (KeymapMessage::Quit(QuitMode::Force), None),
+        "redo" => (KeymapMessage::Buffer(BufferMessage::Redo), None),
+        "replay_macro" => (KeymapMessage::ReplayMacro(' '), expects_char),
+        "restore_from_junkyard" => (
+            KeymapMessage::RestoreFromJunkYard(' '),
+            Some(NextBindingKind::Raw(None)),
+        ),
+        "save" => (KeymapMessage::Buffer(BufferMessage::SaveBuffer), None),
+        "set_mark" => (KeymapMessage::SetMark(' '), expects_char),
+        "start_macro" => {
+            return Ok(Binding {
+                expects: expects_char,
+                kind: BindingKind::Message(KeymapMessage::StartMacro(' ')),
+                repeatable: false,
+                toggle: Some((
+                    "macro-toggle".to_owned(),
+                    BindingKind::Message(KeymapMessage::StopMacro),
+                )),
+                ..Default::default()
+            })
+        }
+        "stop_macro" => (KeymapMessage::StopMacro, None),
+        "toggle_quickfix" => (KeymapMessage::ToggleQuickFix, None),
+        "undo" => (KeymapMessage::Buffer(BufferMessage::Undo), None),
+        "viewport_bottom_on_cursor" => (
+            KeymapMessage::Buffer(BufferMessage::MoveViewPort(
+                ViewPortDirection::BottomOnCursor,
+            )),
+            None,
+        ),
+        "viewport_center_on_cursor" => (
+            KeymapMessage::Buffer(BufferMessage::MoveViewPort(
+                ViewPortDirection::CenterOnCursor,
+            )),
+            None,
+        ),
+        "viewport_half_page_down" => (
+            KeymapMessage::Buffer(BufferMessage::MoveViewPort(ViewPortDirection::HalfPageDown)),
+            None,
+        ),
+        "viewport_half_page_up" => (
+            KeymapMessage::Buffer(BufferMessage::MoveViewPort(ViewPortDirection::HalfPageUp)),
+            None,
+        ),
+        "viewport_top_on_cursor" => (
+            KeymapMessage::Buffer(BufferMessage::MoveViewPort(ViewPortDirection::TopOnCursor)),
+            None,
+        ),
+        "yank_path_to_clipboard" => (KeymapMessage::YankPathToClipboard, None),
+        "yank_to_junkyard" => (KeymapMessage::YankToJunkYard(0), None),
+        _ => return Err(KeyMapError::MappingTargetUnknown(name.to_owned())),
+    };
+
+    Ok(Binding {
+        expects,
+        kind: BindingKind::Message(message),
+        ..Default::default()
+    })
+}
+
+fn to_motion_binding(name: &str) -> Result<Binding, KeyMapError> {
+    let raw = Some(NextBindingKind::Raw(None));
+    let (motion, expects) = match name {
+        "bottom" => (CursorDirection::Bottom, None),
+        "down" => (CursorDirection::Down, None),
+        "find_backward" => (CursorDirection::FindBackward('_'), raw),
+        "find_forward" => (CursorDirection::FindForward('_'), raw),
+        "last_find_backward" => (CursorDirection::LastFindBackward, None),
+        "last_find_forward" => (CursorDirection::LastFindForward, None),
+        "left" => (CursorDirection::Left, None),
+        "line_end" => (CursorDirection::LineEnd, None),
+        "line_start" => (CursorDirection::LineStart, None),
+        "right" => (CursorDirection::Right, None),
+        "search_next" => (CursorDirection::Search(Search::Next), None),
+        "search_previous" => (CursorDirection::Search(Search::Previous), None),
+        "till_backward" => (CursorDirection::TillBackward('_'), raw),
+        "till_forward" => (CursorDirection::TillForward('_'), raw),
+        "top" => (CursorDirection::Top, None),
+        "up" => (CursorDirection::Up, None),
+        "word_end_backward" => (CursorDirection::WordEndBackward, None),
+        "word_end_forward" => (CursorDirection::WordEndForward, None),
+        "word_start_backward" => (CursorDirection::WordStartBackward, None),
+        "word_start_forward" => (CursorDirection::WordStartForward, None),
+        "word_upper_end_backward" => (CursorDirection::WordUpperEndBackward, None),
+        "word_upper_end_forward" => (CursorDirection::WordUpperEndForward, None),
+        "word_upper_start_backward" => (CursorDirection::WordUpperStartBackward, None),
+        "word_upper_start_forward" => (CursorDirection::WordUpperStartForward, None),
+        _ => return Err(KeyMapError::MappingTargetUnknown(name.to_owned())),
+    };
+
+    Ok(Binding {
+        expects,
+        kind: BindingKind::Motion(motion),
+        ..Default::default()
+    })
+}
+
+fn to_mode(name: &str) -> Result<Mode, KeyMapError> {
+    match name {
+        "command" => Ok(Mode::Command(CommandMode::Command)),
+        "insert" => Ok(Mode::Insert),
+        "navigation" => Ok(Mode::Navigation),
+        "normal" => Ok(Mode::Normal),
+        "search_down" => Ok(Mode::Command(CommandMode::Search(SearchDirection::Down))),
+        "search_up" => Ok(Mode::Command(CommandMode::Search(SearchDirection::Up))),
+        "visual_line" => Ok(Mode::VisualLine),
+        _ => Err(KeyMapError::MappingTargetUnknown(name.to_owned())),
+    }
+}
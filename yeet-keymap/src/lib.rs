@@ -11,6 +11,7 @@ use yeet_buffer::{
 };
 
 mod buffer;
+mod config;
 pub mod conversion;
 pub mod key;
 mod map;
@@ -18,9 +19,17 @@ pub mod message;
 mod tree;
 
 #[derive(Debug, thiserror::Error, PartialEq)]
-enum KeyMapError {
+pub enum KeyMapError {
+    #[error("Keymap configuration is invalid: {0}")]
+    ConfigurationInvalid(String),
     #[error("Key sequence is incomplete.")]
     KeySequenceIncomplete,
+    #[error("Key sequence '{0}' is invalid.")]
+    KeySequenceInvalid(String),
+    #[error("Mapping '{1}' conflicts with existing mappings in mode {0}.")]
+    MappingConflict(String, String),
+    #[error("Mapping target '{0}' is unknown.")]
+    MappingTargetUnknown(String),
     #[error("Failed to add mapping for mode {0}.")]
     ModeUnresolvable(String),
     #[error("Failed to resolve valid binding.")]
@@ -38,16 +47,36 @@ pub struct MessageResolver {
 
 impl Default for MessageResolver {
     fn default() -> Self {
+        let (tree, errors) = KeyMap::default().into_tree();
+        for error in errors {
+            tracing::error!("default mappings must form a valid tree: {:?}", error);
+        }
+
         Self {
             buffer: KeyBuffer::default(),
             mode: Mode::default(),
             toggle: HashSet::new(),
-            tree: KeyMap::default().into_tree(),
+            tree,
         }
     }
 }
 
 impl MessageResolver {
+    pub fn add_user_mappings(&mut self, content: &str) -> Vec<KeyMapError> {
+        let (mappings, mut errors) = config::from_toml(content);
+
+        let mut keymap = KeyMap::default();
+        keymap.merge(mappings);
+
+        let (tree, tree_errors) = keymap.into_tree();
+        errors.extend(tree_errors);
+
+        self.tree = tree;
+        self.buffer.clear();
+
+        errors
+    }
+
     pub fn add_keys(
         &mut self,
         mut keys: VecDeque<Key>,
@@ -247,6 +276,7 @@ fn combine(current: &Binding, next: &Binding) -> Result<BindingKind, KeyMapError
                 KeymapMessage::PasteFromJunkYard(_) => KeymapMessage::PasteFromJunkYard(*raw),
                 KeymapMessage::NavigateToMark(_) => KeymapMessage::NavigateToMark(*raw),
                 KeymapMessage::ReplayMacro(_) => KeymapMessage::ReplayMacro(*raw),
+                KeymapMessage::RestoreFromJunkYard(_) => KeymapMessage::RestoreFromJunkYard(*raw),
                 KeymapMessage::SetMark(_) => KeymapMessage::SetMark(*raw),
                 KeymapMessage::StartMacro(_) => KeymapMessage::StartMacro(*raw),
                 _ => return Err(KeyMapError::NoValidBindingFound),
@@ -352,6 +382,6 @@ fn get_repeated_message(repeat: usize, msg: &KeymapMessage) -> Vec<KeymapMessage
 fn get_passthrough_by_mode(mode: &Mode) -> bool {
     match mode {
         Mode::Command(_) | Mode::Insert => true,
-        Mode::Navigation | Mode::Normal => false,
+        Mode::Navigation | Mode::Normal | Mode::VisualLine => false,
     }
 }
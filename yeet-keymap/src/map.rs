@@ -9,9 +9,11 @@ use yeet_buffer::{
 };
 
 use crate::{
+    config::UserMapping,
     key::{Key, KeyCode, KeyModifier},
     message::{Binding, BindingKind, KeymapMessage, NextBindingKind},
     tree::KeyTree,
+    KeyMapError,
 };
 
 pub struct KeyMap {
@@ -19,15 +21,30 @@ pub struct KeyMap {
 }
 
 impl KeyMap {
-    pub fn into_tree(self) -> KeyTree {
+    pub fn into_tree(self) -> (KeyTree, Vec<KeyMapError>) {
         let mut tree = KeyTree::default();
+        let mut errors = Vec::new();
         for (mode, mappings) in self.mappings {
             for (keys, message) in mappings {
-                tree.add_mapping(&mode, keys, message)
-                    .expect("Default mappings must form a valid tree.");
+                if let Err(error) = tree.add_mapping(&mode, keys, message) {
+                    errors.push(error);
+                }
+            }
+        }
+        (tree, errors)
+    }
+
+    pub fn merge(&mut self, mappings: Vec<UserMapping>) {
+        for mapping in mappings {
+            for mode in mapping.modes {
+                let mappings_for_mode = self.mappings.entry(mode).or_default();
+                mappings_for_mode.retain(|(keys, _)| keys != &mapping.keys);
+
+                if let Some(binding) = &mapping.binding {
+                    mappings_for_mode.push((mapping.keys.clone(), binding.clone()));
+                }
             }
         }
-        tree
     }
 }
 
@@ -127,6 +144,17 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![
+                        Key::new(KeyCode::from_char('"'), vec![]),
+                        Key::new(KeyCode::from_char('r'), vec![]),
+                    ],
+                    Binding {
+                        expects: Some(NextBindingKind::Raw(None)),
+                        kind: BindingKind::Message(KeymapMessage::RestoreFromJunkYard(' ')),
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![Key::new(KeyCode::Enter, vec![])],
                     Binding {
@@ -194,6 +222,14 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![Key::new(KeyCode::from_char('v'), vec![KeyModifier::Shift])],
+                    Binding {
+                        force: Some(Mode::VisualLine),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![
                         Key::new(KeyCode::from_char('y'), vec![]),
@@ -214,6 +250,19 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![
+                        Key::new(KeyCode::from_char('z'), vec![]),
+                        Key::new(KeyCode::from_char('h'), vec![]),
+                    ],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::ExecuteCommandString(
+                            "set show_hidden!".to_owned(),
+                        )),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
             ],
         );
 
@@ -222,260 +271,267 @@ impl Default for KeyMap {
             vec![Mode::Navigation, Mode::Normal],
             vec![
                 (
-                    vec![Key::new(KeyCode::Space, vec![])],
+                    vec![Key::new(KeyCode::from_char('@'), vec![])],
                     Binding {
-                        kind: BindingKind::Message(KeymapMessage::ToggleQuickFix),
-                        repeatable: false,
+                        expects: Some(NextBindingKind::Raw(Some(
+                            Regex::new("[[:alpha:]]").expect("Invalid regex"),
+                        ))),
+                        kind: BindingKind::Message(KeymapMessage::ReplayMacro(' ')),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::Esc, vec![])],
+                    vec![
+                        Key::new(KeyCode::from_char('@'), vec![]),
+                        Key::new(KeyCode::from_char('@'), vec![]),
+                    ],
                     Binding {
-                        force: Some(Mode::Navigation),
+                        kind: BindingKind::Message(KeymapMessage::ReplayMacro('@')),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('1'), vec![])],
+                    vec![Key::new(KeyCode::from_char('\''), vec![])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(1),
+                        expects: Some(NextBindingKind::Raw(None)),
+                        kind: BindingKind::Message(KeymapMessage::NavigateToMark(' ')),
+                        repeatable: false,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('2'), vec![])],
+                    vec![Key::new(KeyCode::from_char('/'), vec![])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(2),
+                        force: Some(Mode::Command(CommandMode::Search(SearchDirection::Down))),
+                        repeatable: false,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('3'), vec![])],
+                    vec![Key::new(KeyCode::from_char('?'), vec![])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(3),
+                        force: Some(Mode::Command(CommandMode::Search(SearchDirection::Up))),
+                        repeatable: false,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('4'), vec![])],
+                    vec![Key::new(KeyCode::from_char('a'), vec![KeyModifier::Shift])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(4),
+                        force: Some(Mode::Insert),
+                        kind: BindingKind::Motion(CursorDirection::LineEnd),
+                        repeat: None,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('5'), vec![])],
+                    vec![Key::new(KeyCode::from_char('i'), vec![KeyModifier::Shift])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(5),
+                        force: Some(Mode::Insert),
+                        kind: BindingKind::Motion(CursorDirection::LineStart),
+                        repeat: None,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('6'), vec![])],
+                    vec![Key::new(KeyCode::from_char('m'), vec![])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(6),
+                        expects: Some(NextBindingKind::Raw(Some(
+                            Regex::new("[[:alpha:]]").expect("Invalid regex"),
+                        ))),
+                        kind: BindingKind::Message(KeymapMessage::SetMark(' ')),
+                        repeatable: false,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('7'), vec![])],
+                    vec![Key::new(KeyCode::from_char('o'), vec![])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(7),
+                        force: Some(Mode::Insert),
+                        kind: BindingKind::Modification(TextModification::InsertNewLine(
+                            LineDirection::Down,
+                        )),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('8'), vec![])],
+                    vec![Key::new(KeyCode::from_char('o'), vec![KeyModifier::Shift])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(8),
+                        force: Some(Mode::Insert),
+                        kind: BindingKind::Modification(TextModification::InsertNewLine(
+                            LineDirection::Up,
+                        )),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('9'), vec![])],
+                    vec![Key::new(KeyCode::from_char('q'), vec![])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(9),
+                        expects: Some(NextBindingKind::Raw(Some(
+                            Regex::new("[[:alpha:]]").expect("Invalid regex"),
+                        ))),
+                        kind: BindingKind::Message(KeymapMessage::StartMacro(' ')),
+                        repeatable: false,
+                        toggle: Some((
+                            "macro-toggle".to_owned(),
+                            BindingKind::Message(KeymapMessage::StopMacro),
+                        )),
                         ..Default::default()
                     },
                 ),
+            ],
+        );
+
+        add_mapping(
+            &mut mappings,
+            vec![Mode::Navigation, Mode::Normal, Mode::VisualLine],
+            vec![
                 (
-                    vec![Key::new(KeyCode::from_char('@'), vec![])],
+                    vec![Key::new(KeyCode::Space, vec![])],
                     Binding {
-                        expects: Some(NextBindingKind::Raw(Some(
-                            Regex::new("[[:alpha:]]").expect("Invalid regex"),
-                        ))),
-                        kind: BindingKind::Message(KeymapMessage::ReplayMacro(' ')),
+                        kind: BindingKind::Message(KeymapMessage::ToggleQuickFix),
+                        repeatable: false,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![
-                        Key::new(KeyCode::from_char('@'), vec![]),
-                        Key::new(KeyCode::from_char('@'), vec![]),
-                    ],
+                    vec![Key::new(KeyCode::Esc, vec![])],
                     Binding {
-                        kind: BindingKind::Message(KeymapMessage::ReplayMacro('@')),
+                        force: Some(Mode::Navigation),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('\''), vec![])],
+                    vec![Key::new(KeyCode::from_char('1'), vec![])],
                     Binding {
-                        expects: Some(NextBindingKind::Raw(None)),
-                        kind: BindingKind::Message(KeymapMessage::NavigateToMark(' ')),
-                        repeatable: false,
+                        kind: BindingKind::Repeat,
+                        repeat: Some(1),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char(':'), vec![])],
+                    vec![Key::new(KeyCode::from_char('2'), vec![])],
                     Binding {
-                        force: Some(Mode::Command(CommandMode::Command)),
-                        repeatable: false,
+                        kind: BindingKind::Repeat,
+                        repeat: Some(2),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('/'), vec![])],
+                    vec![Key::new(KeyCode::from_char('3'), vec![])],
                     Binding {
-                        force: Some(Mode::Command(CommandMode::Search(SearchDirection::Down))),
-                        repeatable: false,
+                        kind: BindingKind::Repeat,
+                        repeat: Some(3),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('?'), vec![])],
+                    vec![Key::new(KeyCode::from_char('4'), vec![])],
                     Binding {
-                        force: Some(Mode::Command(CommandMode::Search(SearchDirection::Up))),
-                        repeatable: false,
+                        kind: BindingKind::Repeat,
+                        repeat: Some(4),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('a'), vec![KeyModifier::Shift])],
+                    vec![Key::new(KeyCode::from_char('5'), vec![])],
                     Binding {
-                        force: Some(Mode::Insert),
-                        kind: BindingKind::Motion(CursorDirection::LineEnd),
-                        repeat: None,
+                        kind: BindingKind::Repeat,
+                        repeat: Some(5),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('d'), vec![KeyModifier::Ctrl])],
+                    vec![Key::new(KeyCode::from_char('6'), vec![])],
                     Binding {
-                        kind: BindingKind::Message(KeymapMessage::Buffer(
-                            BufferMessage::MoveViewPort(ViewPortDirection::HalfPageDown),
-                        )),
+                        kind: BindingKind::Repeat,
+                        repeat: Some(6),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('g'), vec![KeyModifier::Shift])],
+                    vec![Key::new(KeyCode::from_char('7'), vec![])],
                     Binding {
-                        kind: BindingKind::Motion(CursorDirection::Bottom),
+                        kind: BindingKind::Repeat,
+                        repeat: Some(7),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![
-                        Key::new(KeyCode::from_char('g'), vec![]),
-                        Key::new(KeyCode::from_char('g'), vec![]),
-                    ],
+                    vec![Key::new(KeyCode::from_char('8'), vec![])],
                     Binding {
-                        kind: BindingKind::Motion(CursorDirection::Top),
+                        kind: BindingKind::Repeat,
+                        repeat: Some(8),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('i'), vec![KeyModifier::Shift])],
+                    vec![Key::new(KeyCode::from_char('9'), vec![])],
                     Binding {
-                        force: Some(Mode::Insert),
-                        kind: BindingKind::Motion(CursorDirection::LineStart),
-                        repeat: None,
+                        kind: BindingKind::Repeat,
+                        repeat: Some(9),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('j'), vec![])],
+                    vec![Key::new(KeyCode::from_char(':'), vec![])],
                     Binding {
-                        kind: BindingKind::Motion(CursorDirection::Down),
+                        force: Some(Mode::Command(CommandMode::Command)),
+                        repeatable: false,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('k'), vec![])],
+                    vec![Key::new(KeyCode::from_char('d'), vec![KeyModifier::Ctrl])],
                     Binding {
-                        kind: BindingKind::Motion(CursorDirection::Up),
+                        kind: BindingKind::Message(KeymapMessage::Buffer(
+                            BufferMessage::MoveViewPort(ViewPortDirection::HalfPageDown),
+                        )),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('m'), vec![])],
+                    vec![Key::new(KeyCode::from_char('g'), vec![KeyModifier::Shift])],
                     Binding {
-                        expects: Some(NextBindingKind::Raw(Some(
-                            Regex::new("[[:alpha:]]").expect("Invalid regex"),
-                        ))),
-                        kind: BindingKind::Message(KeymapMessage::SetMark(' ')),
-                        repeatable: false,
+                        kind: BindingKind::Motion(CursorDirection::Bottom),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('n'), vec![])],
+                    vec![
+                        Key::new(KeyCode::from_char('g'), vec![]),
+                        Key::new(KeyCode::from_char('g'), vec![]),
+                    ],
                     Binding {
-                        kind: BindingKind::Motion(CursorDirection::Search(Search::Next)),
+                        kind: BindingKind::Motion(CursorDirection::Top),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('n'), vec![KeyModifier::Shift])],
+                    vec![Key::new(KeyCode::from_char('j'), vec![])],
                     Binding {
-                        kind: BindingKind::Motion(CursorDirection::Search(Search::Previous)),
+                        kind: BindingKind::Motion(CursorDirection::Down),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('o'), vec![])],
+                    vec![Key::new(KeyCode::from_char('k'), vec![])],
                     Binding {
-                        force: Some(Mode::Insert),
-                        kind: BindingKind::Modification(TextModification::InsertNewLine(
-                            LineDirection::Down,
-                        )),
+                        kind: BindingKind::Motion(CursorDirection::Up),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('o'), vec![KeyModifier::Shift])],
+                    vec![Key::new(KeyCode::from_char('n'), vec![])],
                     Binding {
-                        force: Some(Mode::Insert),
-                        kind: BindingKind::Modification(TextModification::InsertNewLine(
-                            LineDirection::Up,
-                        )),
+                        kind: BindingKind::Motion(CursorDirection::Search(Search::Next)),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('q'), vec![])],
+                    vec![Key::new(KeyCode::from_char('n'), vec![KeyModifier::Shift])],
                     Binding {
-                        expects: Some(NextBindingKind::Raw(Some(
-                            Regex::new("[[:alpha:]]").expect("Invalid regex"),
-                        ))),
-                        kind: BindingKind::Message(KeymapMessage::StartMacro(' ')),
-                        repeatable: false,
-                        toggle: Some((
-                            "macro-toggle".to_owned(),
-                            BindingKind::Message(KeymapMessage::StopMacro),
-                        )),
+                        kind: BindingKind::Motion(CursorDirection::Search(Search::Previous)),
                         ..Default::default()
                     },
                 ),
@@ -527,6 +583,37 @@ impl Default for KeyMap {
             ],
         );
 
+        add_mapping(
+            &mut mappings,
+            vec![Mode::VisualLine],
+            vec![
+                (
+                    vec![Key::new(KeyCode::from_char('d'), vec![])],
+                    Binding {
+                        kind: BindingKind::Modification(TextModification::DeleteLine),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('v'), vec![KeyModifier::Shift])],
+                    Binding {
+                        force: Some(Mode::Navigation),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('y'), vec![])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::YankToJunkYard(0)),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
+            ],
+        );
+
         add_mapping(
             &mut mappings,
             vec![Mode::Normal],
@@ -647,6 +734,13 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![Key::new(KeyCode::from_char('r'), vec![KeyModifier::Ctrl])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::Buffer(BufferMessage::Redo)),
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![Key::new(KeyCode::from_char('s'), vec![])],
                     Binding {
@@ -674,6 +768,13 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![Key::new(KeyCode::from_char('u'), vec![])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::Buffer(BufferMessage::Undo)),
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![Key::new(KeyCode::from_char('x'), vec![])],
                     Binding {
@@ -105,6 +105,7 @@ pub enum KeymapMessage {
     PasteFromJunkYard(char),
     Print(Vec<PrintContent>),
     ReplayMacro(char),
+    RestoreFromJunkYard(char),
     SetMark(char),
     StartMacro(char),
     StopMacro,
@@ -27,13 +27,17 @@ impl KeyTree {
             self.modes.insert(mode.clone(), Node::Key(HashMap::new()));
         }
 
+        if keys.is_empty() {
+            return Err(KeyMapError::KeySequenceInvalid(String::new()));
+        }
+
         let max_index = keys.len() - 1;
         let mut iter = keys.iter().enumerate();
         match self.modes.get_mut(mode) {
-            Some(node) => {
-                add_mapping_node(&max_index, &mut iter, node, binding);
-                Ok(())
-            }
+            Some(node) => add_mapping_node(&max_index, &mut iter, node, binding).map_err(|_| {
+                let sequence = keys.iter().map(|key| key.to_keycode_string()).collect();
+                KeyMapError::MappingConflict(mode.to_string(), sequence)
+            }),
             None => Err(KeyMapError::ModeUnresolvable(mode.to_string())),
         }
     }
@@ -63,41 +67,41 @@ fn add_mapping_node(
     iter: &mut Enumerate<Iter<'_, Key>>,
     node: &mut Node,
     binding: Binding,
-) {
+) -> Result<(), KeyMapError> {
     if let Some((index, key)) = iter.next() {
         if &index == max_index {
             match node {
-                Node::Binding(_) => unreachable!(),
-                Node::ExpectsOr(_, map) | Node::Key(map) => {
-                    if binding.expects.is_some() {
-                        if let Some(node) = map.remove(key) {
-                            match node {
-                                Node::Binding(_) | Node::ExpectsOr(_, _) => unreachable!(),
-                                Node::Key(m) => {
-                                    map.insert(key.clone(), Node::ExpectsOr(binding, m.clone()));
-                                }
-                            }
+                Node::Binding(_) => return Err(KeyMapError::NoValidBindingFound),
+                Node::ExpectsOr(_, map) | Node::Key(map) => match map.get(key) {
+                    Some(Node::Key(m)) if binding.expects.is_some() => {
+                        let node = Node::ExpectsOr(binding, m.clone());
+                        map.insert(key.clone(), node);
+                    }
+                    Some(_) => return Err(KeyMapError::NoValidBindingFound),
+                    None => {
+                        let node = if binding.expects.is_some() {
+                            Node::ExpectsOr(binding, HashMap::new())
                         } else {
-                            map.insert(key.clone(), Node::ExpectsOr(binding, HashMap::new()));
-                        }
-                    } else if map.insert(key.clone(), Node::Binding(binding)).is_some() {
-                        panic!("This should not happen");
+                            Node::Binding(binding)
+                        };
+                        map.insert(key.clone(), node);
                     }
-                }
+                },
             }
         } else {
             match node {
-                Node::Binding(_) => unreachable!(),
+                Node::Binding(_) => return Err(KeyMapError::NoValidBindingFound),
                 Node::ExpectsOr(_, map) | Node::Key(map) => {
                     if !map.contains_key(key) {
                         map.insert(key.clone(), Node::Key(HashMap::new()));
                     }
                     let node = map.get_mut(key).expect("Must exist");
-                    add_mapping_node(max_index, iter, node, binding);
+                    add_mapping_node(max_index, iter, node, binding)?;
                 }
             }
         }
     }
+    Ok(())
 }
 
 fn get_bindings_from_node(node: &Node, iter: &mut Iter<'_, Key>) -> Result<Node, KeyMapError> {
@@ -247,6 +247,23 @@ fn add_and_resolve_key_navigation_10yy() {
     assert_eq!(1, result.0.len());
 }
 
+#[test]
+fn add_and_resolve_key_navigation_quote_r1() {
+    let mut resolver = MessageResolver::default();
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('"'), vec![]));
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('r'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('1'), vec![]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::RestoreFromJunkYard('1')),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("\"r1".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
 #[test]
 fn add_and_resolve_key_normal_0() {
     let mut resolver = MessageResolver::default();
@@ -360,3 +377,106 @@ fn add_and_resolve_key_normal_qa_and_q() {
     assert_eq!(KeySequence::Completed("q".to_string()), result.1);
     assert_eq!(1, result.0.len());
 }
+
+#[test]
+fn add_and_resolve_key_visual_line_jd() {
+    let mut resolver = MessageResolver::default();
+    let result = resolver.add_key(Key::new(KeyCode::from_char('v'), vec![KeyModifier::Shift]));
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::ChangeMode(
+            Mode::Navigation,
+            Mode::VisualLine
+        ))),
+        result.0.first()
+    );
+
+    resolver.mode = Mode::VisualLine;
+
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('2'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('j'), vec![]));
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::MoveCursor(
+            2,
+            CursorDirection::Down
+        ))),
+        result.0.first()
+    );
+
+    let result = resolver.add_key(Key::new(KeyCode::from_char('d'), vec![]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::Modification(
+            1,
+            TextModification::DeleteLine
+        ))),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("d".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_user_mappings_and_resolve_key_normal_remapped() {
+    let mut resolver = MessageResolver::default();
+    let errors = resolver.add_user_mappings(
+        r#"
+        [normal]
+        "<C-e>" = { motion = "down" }
+        "x" = "none"
+        "#,
+    );
+
+    assert!(errors.is_empty());
+
+    resolver.mode = Mode::Normal;
+    let result = resolver.add_key(Key::new(KeyCode::from_char('e'), vec![KeyModifier::Ctrl]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::MoveCursor(
+            1,
+            CursorDirection::Down
+        ))),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("<C-e>".to_string()), result.1);
+
+    let result = resolver.add_key(Key::new(KeyCode::from_char('x'), vec![]));
+
+    println!("{:?}", result);
+
+    assert!(result.0.is_empty());
+}
+
+#[test]
+fn add_user_mappings_with_conflict() {
+    let mut resolver = MessageResolver::default();
+    let errors = resolver.add_user_mappings(
+        r#"
+        [navigation]
+        "g" = { command = "tl" }
+        "zz" = { message = "unknown" }
+        "#,
+    );
+
+    assert_eq!(2, errors.len());
+
+    resolver.mode = Mode::Navigation;
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('g'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('g'), vec![]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::MoveCursor(
+            1,
+            CursorDirection::Top
+        ))),
+        result.0.first()
+    );
+}
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
 use thiserror::Error;
 use tracing::Level;
-use yeet_frontend::settings::Settings;
+use yeet_frontend::{error::AppError, remote, settings::Settings};
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -26,6 +26,10 @@ async fn main() {
             .init();
     }
 
+    if let Some(request) = cli.get_one::<String>("remote") {
+        std::process::exit(send_remote(request).await);
+    }
+
     tracing::info!("starting application");
 
     let default_panic = std::panic::take_hook();
@@ -35,7 +39,9 @@ async fn main() {
         std::process::exit(1);
     }));
 
-    match yeet_frontend::run(get_settings(&cli)).await {
+    let (settings, errors) = get_settings(&cli);
+
+    match yeet_frontend::run(settings, errors).await {
         Ok(()) => {
             tracing::info!("closing application");
         }
@@ -55,6 +61,11 @@ fn cli() -> Command {
                 .value_parser(value_parser!(PathBuf))
                 .help("path to open in yeet on startup"),
             // NOTE: options
+            Arg::new("remote")
+                .long("remote")
+                .action(ArgAction::Set)
+                .value_name("request")
+                .help("send a json request or command to a running instance and print the response"),
             Arg::new("selection-to-file-on-open")
                 .long("selection-to-file-on-open")
                 .action(ArgAction::Set)
@@ -74,6 +85,33 @@ fn cli() -> Command {
         ])
 }
 
+async fn send_remote(request: &str) -> i32 {
+    let response = match remote::to_request(request) {
+        Ok(request) => remote::send(&request).await,
+        Err(err) => Err(err),
+    };
+
+    match response {
+        Ok(response) => {
+            match serde_json::to_string(&response) {
+                Ok(content) => println!("{}", content),
+                Err(err) => eprintln!("yeet: {}", err),
+            }
+
+            if response.ok {
+                0
+            } else {
+                1
+            }
+        }
+        Err(err) => {
+            tracing::error!("sending remote request failed: {:?}", err);
+            eprintln!("yeet: {:?}", err);
+            1
+        }
+    }
+}
+
 fn get_log_level(args: &ArgMatches) -> Level {
     match args
         .get_one::<String>("verbosity")
@@ -101,11 +139,29 @@ fn get_logging_path() -> Result<String, Error> {
     Ok(format!("{}{}", cache_dir, "/yeet/logs"))
 }
 
-fn get_settings(args: &ArgMatches) -> Settings {
-    Settings {
-        selection_to_file_on_open: args.get_one("selection-to-file-on-open").cloned(),
-        selection_to_stdout_on_open: args.get_flag("selection-to-stdout-on-open"),
-        startup_path: args.get_one("path").cloned(),
-        ..Default::default()
+// NOTE: invalid config files fall back to defaults, errors get shown in the commandline
+fn get_settings(args: &ArgMatches) -> (Settings, Vec<AppError>) {
+    let mut errors = Vec::new();
+    let mut settings = match Settings::from_config_file() {
+        Ok(it) => it,
+        Err(err) => {
+            tracing::error!("loading settings failed: {:?}", err);
+            errors.push(err);
+            Settings::default()
+        }
+    };
+
+    if let Some(path) = args.get_one("selection-to-file-on-open").cloned() {
+        settings.selection_to_file_on_open = Some(path);
     }
+
+    if args.get_flag("selection-to-stdout-on-open") {
+        settings.selection_to_stdout_on_open = true;
+    }
+
+    if let Some(path) = args.get_one("path").cloned() {
+        settings.startup_path = Some(path);
+    }
+
+    (settings, errors)
 }